@@ -20,9 +20,11 @@ use comrak::{
 };
 use std::ops::{Range, RangeInclusive};
 
+use crate::directives::{Directives, IgnoreComment};
+
 /// Parse the document, populate the `arena` variable with the nodes, and return the root node of the AST.
 ///
-/// All optional parse options not related to GitHub Flavored Markdown tables are turned off.
+/// All optional parse options not related to GitHub Flavored Markdown tables are turned off, except for footnotes since footnote definitions can contain tables.
 fn get_ast<'ast>(arena: &'ast Arena<AstNode<'ast>>, doc: &str) -> &'ast AstNode<'ast> {
     // The returned nodes are created in the supplied Arena, and are bound by its lifetime.
 
@@ -34,7 +36,7 @@ fn get_ast<'ast>(arena: &'ast Arena<AstNode<'ast>>, doc: &str) -> &'ast AstNode<
     extension_options.tasklist = false;
     extension_options.superscript = false;
     extension_options.header_ids = None;
-    extension_options.footnotes = false;
+    extension_options.footnotes = true;
     extension_options.description_lists = false;
     extension_options.front_matter_delimiter = None;
 
@@ -47,19 +49,32 @@ fn get_ast<'ast>(arena: &'ast Arena<AstNode<'ast>>, doc: &str) -> &'ast AstNode<
     parse_document(arena, doc, &options)
 }
 
-/// Return all table AST nodes along with the table alignment vector
-fn search_children(
-    nodes: Children<'_, std::cell::RefCell<Ast>>,
-) -> Vec<(Vec<TableAlignment>, &AstNode<'_>)> {
-    let mut tables: Vec<(Vec<TableAlignment>, &AstNode<'_>)> = vec![];
+/// Return the directives in the HTML comment immediately preceding `node`, if there is one.
+fn get_directives(node: &AstNode<'_>) -> Directives {
+    let Some(previous) = node.previous_sibling() else {
+        return Directives::default();
+    };
+
+    match &previous.data.borrow().value {
+        NodeValue::HtmlBlock(html) => Directives::parse(&html.literal).unwrap_or_default(),
+        _ => Directives::default(),
+    }
+}
+
+/// A table AST node along with the table alignment vector and its directives
+type TableNode<'a> = (Vec<TableAlignment>, Directives, &'a AstNode<'a>);
+
+/// Return all table AST nodes along with the table alignment vector and directives
+fn search_children(nodes: Children<'_, std::cell::RefCell<Ast>>) -> Vec<TableNode<'_>> {
+    let mut tables: Vec<TableNode<'_>> = vec![];
 
     for node in nodes {
         match &node.data.borrow().value {
             NodeValue::Document => unreachable!("This only appears at the tree root"),
             NodeValue::FrontMatter(_) => unreachable!(), // `front_matter_delimeter` is `None` in `extension_options`
             NodeValue::BlockQuote => tables.append(&mut search_children(node.children())),
-            NodeValue::List(_) => (), // tables shouldn't ever be in a list
-            NodeValue::Item(_) => (), // tables shouldn't ever be in a list item
+            NodeValue::List(_) => tables.append(&mut search_children(node.children())),
+            NodeValue::Item(_) => tables.append(&mut search_children(node.children())),
             NodeValue::DescriptionList => {
                 unreachable!("DescriptionLists is `false` in `extension_options`")
             }
@@ -78,11 +93,13 @@ fn search_children(
             NodeValue::Heading(_) => (), // tables cannot be nested in a heading since they require multiple lines
             NodeValue::ThematicBreak => (), // has no children
             NodeValue::FootnoteDefinition(_) => {
-                unreachable!("`footnotes` are not turned on in `extension_options`")
-            }
-            NodeValue::Table(table_metadata) => {
-                tables.push((table_metadata.alignments.clone(), node))
+                tables.append(&mut search_children(node.children()))
             }
+            NodeValue::Table(table_metadata) => tables.push((
+                table_metadata.alignments.clone(),
+                get_directives(node),
+                node,
+            )),
             NodeValue::TableRow(_) => (), // we are only interested in the top level table
             NodeValue::TableCell => (),   // we are only interested in the top level table
             NodeValue::Text(_) => (),     // inline item
@@ -97,20 +114,85 @@ fn search_children(
             NodeValue::Superscript => (), // inline item
             NodeValue::Link(_) => (),     // inline item
             NodeValue::Image(_) => (),    // inline item
-            NodeValue::FootnoteReference(_) => {
-                unreachable!("`footnotes` are not turned on in `extension_options`")
-            }
+            NodeValue::FootnoteReference(_) => (), // inline item
         }
     }
 
     tables
 }
 
+/// Return every comment that turns formatting off or on, along with its 1-indexed line, ordered by position.
+fn search_ignore_comments(
+    nodes: Children<'_, std::cell::RefCell<Ast>>,
+) -> Vec<(usize, IgnoreComment)> {
+    let mut comments = vec![];
+
+    for node in nodes {
+        match &node.data.borrow().value {
+            NodeValue::BlockQuote
+            | NodeValue::List(_)
+            | NodeValue::Item(_)
+            | NodeValue::FootnoteDefinition(_) => {
+                comments.append(&mut search_ignore_comments(node.children()))
+            }
+            NodeValue::HtmlBlock(html) => {
+                if let Some(comment) = IgnoreComment::parse(&html.literal) {
+                    comments.push((node.data.borrow().sourcepos.start.line, comment));
+                }
+            }
+            _ => (), // comments that are not HTML blocks, such as inline HTML, are not ignore comments
+        }
+    }
+
+    comments
+}
+
+/// Return the 1-indexed lines of every paragraph along with the byte offset of the start of the paragraph in its first line.
+fn search_paragraphs(
+    nodes: Children<'_, std::cell::RefCell<Ast>>,
+) -> Vec<(RangeInclusive<usize>, usize)> {
+    let mut paragraphs = vec![];
+
+    for node in nodes {
+        match &node.data.borrow().value {
+            NodeValue::BlockQuote
+            | NodeValue::List(_)
+            | NodeValue::Item(_)
+            | NodeValue::FootnoteDefinition(_) => {
+                paragraphs.append(&mut search_paragraphs(node.children()))
+            }
+            NodeValue::Paragraph => {
+                let position = node.data.borrow().sourcepos;
+
+                paragraphs.push((
+                    position.start.line..=position.end.line,
+                    position.start.column.saturating_sub(1),
+                ))
+            }
+            _ => (), // paragraphs are only nested in container blocks
+        }
+    }
+
+    paragraphs
+}
+
 /// All necessary information to format a table
+///
+/// - `range` byte offsets of every line the table is on
+/// - `lines` 1-indexed line numbers of the first and last line of the table
+/// - `text` the text on those lines
+/// - `alignments` alignments parsed from the delimiter row
+/// - `first_line_offset` byte offset of the start of the table in the first line of `text`.  Any text before it is container markup such as blockquote markers, indentation, or a list item marker.
+/// - `lazy_header` whether the header row is a lazy continuation line of a paragraph in a container, so that it starts at the beginning of its line while the following rows are in the container
+/// - `directives` directives from the HTML comment right before the table.  See `directives.rs`.
 pub struct TableInDocument<'a> {
     pub range: Range<usize>,
+    pub lines: RangeInclusive<usize>,
     pub text: &'a str,
     pub alignments: Vec<TableAlignment>,
+    pub first_line_offset: usize,
+    pub(crate) lazy_header: bool,
+    pub(crate) directives: Directives,
 }
 
 /// Get the byte offset range of `doc` that corresponds to the inclusive range of lines.
@@ -144,29 +226,111 @@ fn get_range_from_lines(doc: &str, lines: RangeInclusive<usize>) -> Range<usize>
     start_offset..doc.len()
 }
 
+/// Return whether `prefix` only has container markup: whitespace, blockquote markers, list item markers, and footnote labels.
+fn is_container_markup(prefix: &str) -> bool {
+    let mut rest = prefix;
+
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', '>']);
+
+        if rest.is_empty() {
+            return true;
+        }
+
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+        let marker_end = if rest.starts_with(['-', '*', '+']) {
+            1
+        } else if (1..=9).contains(&digits) && rest[digits..].starts_with(['.', ')']) {
+            digits + 1
+        } else if rest.starts_with("[^") {
+            match rest.find("]:") {
+                Some(index) => index + 2,
+                None => return false,
+            }
+        } else {
+            return false;
+        };
+
+        rest = &rest[marker_end..];
+
+        // a marker is followed by whitespace, or ends the prefix
+        if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+            return false;
+        }
+    }
+}
+
 /// Use Comrak abstract syntax tree to find the locations (and alignments) of all GitHub Flavored Markdown tables in the `doc` string.
-pub fn get_tables(doc: &str) -> Vec<TableInDocument> {
+///
+/// Tables in a region disabled with an ignore comment, or right after a `disable-next-table` comment, are left out.  See `directives.rs`.
+pub fn get_tables(doc: &str) -> Vec<TableInDocument<'_>> {
     let arena: Arena<AstNode<'_>> = Arena::new();
     let ast: &AstNode<'_> = get_ast(&arena, doc);
 
     let mut tables: Vec<TableInDocument> = vec![];
 
-    for (alignments, table_ast) in search_children(ast.children()) {
+    let ignore_comments = search_ignore_comments(ast.children());
+    let mut ignore_comments = ignore_comments.iter().peekable();
+    let mut disabled = false;
+    let mut disable_next_table = false;
+
+    for (alignments, directives, table_ast) in search_children(ast.children()) {
         let position = table_ast.data.borrow().sourcepos;
 
+        while let Some((_, comment)) =
+            ignore_comments.next_if(|(line, _)| *line < position.start.line)
+        {
+            match comment {
+                IgnoreComment::Disable => disabled = true,
+                IgnoreComment::Enable => disabled = false,
+                IgnoreComment::DisableNextTable => disable_next_table = true,
+            }
+        }
+
+        // a `disable-next-table` comment only applies to one table, even one that is already disabled
+        let skip = std::mem::take(&mut disable_next_table);
+        if disabled || skip {
+            continue;
+        }
+
         let lines: RangeInclusive<usize> = position.start.line..=position.end.line;
-        let range = get_range_from_lines(doc, lines);
+        let range = get_range_from_lines(doc, lines.clone());
 
         let text = doc
             .get(range.clone())
             .expect("This should always be at a valid index since we are iterating over chars.");
 
+        // `comrak` columns are 1-indexed byte offsets.  The column of a header row that is a lazy continuation line is the column of its container's content, which doesn't match the text on the line.
+        let first_line_offset = position.start.column.saturating_sub(1);
+        let lazy_header = !text
+            .get(..first_line_offset)
+            .is_some_and(is_container_markup);
+        let first_line_offset = match lazy_header {
+            true => 0,
+            false => first_line_offset,
+        };
+
         tables.push(TableInDocument {
             range,
+            lines,
             text,
             alignments,
+            first_line_offset,
+            lazy_header,
+            directives,
         });
     }
 
     tables
 }
+
+/// Return the 1-indexed lines of every paragraph in the `doc` string, ordered by position.
+///
+/// Each paragraph also has the byte offset of its start in its first line.  Any text before it is container markup such as blockquote markers, indentation, or a list item marker.
+pub(crate) fn get_paragraphs(doc: &str) -> Vec<(RangeInclusive<usize>, usize)> {
+    let arena: Arena<AstNode<'_>> = Arena::new();
+    let ast: &AstNode<'_> = get_ast(&arena, doc);
+
+    search_paragraphs(ast.children())
+}
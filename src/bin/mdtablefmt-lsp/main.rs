@@ -0,0 +1,254 @@
+//! Language Server Protocol server for Markdown Table Formatter.
+//!
+//! Communicates over stdio and supports
+//!
+//! - `textDocument/formatting`
+//! - `textDocument/rangeFormatting`
+//! - diagnostics from `lint_tables`, pushed with `textDocument/publishDiagnostics` whenever a document is opened or changed
+//!
+//! Documents are synchronized in full on every change.
+
+// the binary needs stdio, and the library has different signatures on wasm32
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg(not(target_arch = "wasm32"))]
+
+mod position;
+mod transport;
+
+use markdown_table_formatter::{
+    format_edits, format_edits_in_range, lint_tables, Severity, TextEdit,
+};
+use position::LineIndex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufReader, Write};
+use std::process::ExitCode;
+use transport::{read_message, write_message};
+
+/// JSON-RPC error codes used by this server.
+mod error_code {
+    pub const PARSE_ERROR: i64 = -32700;
+    pub const INVALID_PARAMS: i64 = -32602;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const SERVER_NOT_INITIALIZED: i64 = -32002;
+}
+
+/// Result of handling a request: the JSON result or an error code and message.
+type RequestResult = Result<Value, (i64, String)>;
+
+#[derive(Default)]
+struct Server {
+    documents: HashMap<String, String>,
+    initialized: bool,
+    shutdown_requested: bool,
+}
+
+impl Server {
+    fn handle_request(&mut self, method: &str, params: &Value) -> RequestResult {
+        if !self.initialized && method != "initialize" {
+            return Err((
+                error_code::SERVER_NOT_INITIALIZED,
+                String::from("server is not initialized"),
+            ));
+        }
+
+        match method {
+            "initialize" => {
+                self.initialized = true;
+
+                Ok(json!({
+                    "capabilities": {
+                        "textDocumentSync": 1,
+                        "documentFormattingProvider": true,
+                        "documentRangeFormattingProvider": true,
+                    },
+                    "serverInfo": {
+                        "name": "mdtablefmt-lsp",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }))
+            }
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Ok(Value::Null)
+            }
+            "textDocument/formatting" => self.formatting(params, None),
+            "textDocument/rangeFormatting" => self.formatting(params, Some(&params["range"])),
+            _ => Err((
+                error_code::METHOD_NOT_FOUND,
+                format!("unsupported method '{method}'"),
+            )),
+        }
+    }
+
+    /// Return the outgoing notifications caused by a notification from the client.
+    fn handle_notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.to_string(), text.to_string());
+
+                vec![self.publish_diagnostics(uri)]
+            }
+            "textDocument/didChange" => {
+                // full synchronization, so the last change holds the whole document
+                let changes = params["contentChanges"].as_array();
+                let Some(text) = changes
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                else {
+                    return vec![];
+                };
+                self.documents.insert(uri.to_string(), text.to_string());
+
+                vec![self.publish_diagnostics(uri)]
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+
+                vec![notification(
+                    "textDocument/publishDiagnostics",
+                    json!({ "uri": uri, "diagnostics": [] }),
+                )]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn document(&self, params: &Value) -> Result<&str, (i64, String)> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        self.documents.get(uri).map(String::as_str).ok_or_else(|| {
+            (
+                error_code::INVALID_PARAMS,
+                format!("unknown document '{uri}'"),
+            )
+        })
+    }
+
+    /// Return the edits for `textDocument/formatting`, or `textDocument/rangeFormatting` if `range` is given.
+    fn formatting(&self, params: &Value, range: Option<&Value>) -> RequestResult {
+        let doc = self.document(params)?;
+        let index = LineIndex::new(doc);
+
+        let edits: Vec<TextEdit> = match range {
+            Some(range) => format_edits_in_range(
+                doc,
+                index.offset(&range["start"])..index.offset(&range["end"]),
+            ),
+            None => format_edits(doc),
+        };
+
+        Ok(edits
+            .iter()
+            .map(|edit| {
+                json!({
+                    "range": index.range(edit.start, edit.end),
+                    "newText": edit.new_text,
+                })
+            })
+            .collect())
+    }
+
+    fn publish_diagnostics(&self, uri: &str) -> Value {
+        let doc = self.documents.get(uri).map_or("", String::as_str);
+        let index = LineIndex::new(doc);
+
+        let diagnostics: Vec<Value> = lint_tables(doc)
+            .iter()
+            .map(|diagnostic| {
+                json!({
+                    "range": index.range(diagnostic.range.start, diagnostic.range.end),
+                    "severity": match diagnostic.severity {
+                        Severity::Error => 1,
+                        Severity::Warning => 2,
+                        Severity::Information => 3,
+                    },
+                    "code": diagnostic.rule.id(),
+                    "source": "markdown-table-formatter",
+                    "message": diagnostic.message,
+                })
+            })
+            .collect();
+
+        notification(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": diagnostics }),
+        )
+    }
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn response(id: &Value, result: RequestResult) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        }),
+    }
+}
+
+fn run() -> io::Result<ExitCode> {
+    let mut reader = BufReader::new(io::stdin().lock());
+    let mut writer = io::stdout().lock();
+    let mut server = Server::default();
+
+    while let Some(message) = read_message(&mut reader)? {
+        let message = match message {
+            Ok(message) => message,
+            Err(err) => {
+                // the id of a message that can't be parsed is unknown
+                let result = Err((error_code::PARSE_ERROR, format!("invalid JSON: {err}")));
+                write_message(&mut writer, &response(&Value::Null, result))?;
+                continue;
+            }
+        };
+
+        let Some(method) = message["method"].as_str() else {
+            // responses to requests this server never sends
+            continue;
+        };
+        let params = &message["params"];
+
+        if method == "exit" {
+            break;
+        }
+
+        match message.get("id") {
+            Some(id) => {
+                let result = server.handle_request(method, params);
+                write_message(&mut writer, &response(id, result))?;
+            }
+            None => {
+                for outgoing in server.handle_notification(method, params) {
+                    write_message(&mut writer, &outgoing)?;
+                }
+            }
+        }
+    }
+
+    writer.flush()?;
+
+    if server.shutdown_requested {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(exit_code) => exit_code,
+        Err(err) => {
+            eprintln!("mdtablefmt-lsp: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
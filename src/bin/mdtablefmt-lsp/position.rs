@@ -0,0 +1,63 @@
+//! Conversion between byte offsets and Language Server Protocol positions.
+//!
+//! LSP positions are a 0-indexed line and a 0-indexed character offset counted in UTF-16 code units, while the library works with UTF-8 byte offsets.
+
+use serde_json::{json, Value};
+
+/// Byte offset of the start of every line in a document.
+pub struct LineIndex<'a> {
+    doc: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(doc: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(doc.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        LineIndex { doc, line_starts }
+    }
+
+    /// Return the LSP position of the byte `offset`.
+    pub fn position(&self, offset: usize) -> Value {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let character = self.doc[self.line_starts[line]..offset]
+            .encode_utf16()
+            .count();
+
+        json!({ "line": line, "character": character })
+    }
+
+    /// Return the LSP range of the byte offsets `start..end`.
+    pub fn range(&self, start: usize, end: usize) -> Value {
+        json!({ "start": self.position(start), "end": self.position(end) })
+    }
+
+    /// Return the byte offset of an LSP position.
+    ///
+    /// Positions past the end of a line are clamped to the end of the line, and positions past the end of the document are clamped to the end of the document.
+    pub fn offset(&self, position: &Value) -> usize {
+        let line = position["line"].as_u64().unwrap_or(0) as usize;
+        let character = position["character"].as_u64().unwrap_or(0) as usize;
+
+        let Some(&line_start) = self.line_starts.get(line) else {
+            return self.doc.len();
+        };
+
+        let line_end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.doc.len(), |next| next - 1);
+
+        let mut utf16_count = 0;
+        for (index, character_value) in self.doc[line_start..line_end].char_indices() {
+            if utf16_count >= character {
+                return line_start + index;
+            }
+            utf16_count += character_value.len_utf16();
+        }
+
+        line_end
+    }
+}
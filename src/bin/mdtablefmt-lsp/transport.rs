@@ -0,0 +1,49 @@
+//! JSON-RPC message framing over stdio as defined by the Language Server Protocol base protocol.
+//!
+//! Each message is a header section of `Name: value` lines followed by an empty line and a UTF-8 JSON body.  `Content-Length` is the only required header.
+
+use serde_json::Value;
+use std::io::{self, BufRead, Write};
+
+/// Read the next message, returning `None` at the end of the input.
+///
+/// A body that isn't valid JSON is returned as the inner error, so that the caller can reply with a parse error and keep reading.  Only broken framing is an I/O error.
+pub fn read_message<R: BufRead>(reader: &mut R) -> io::Result<Option<serde_json::Result<Value>>> {
+    let mut content_length: Option<usize> = None;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let header = line.trim_end_matches(['\r', '\n']);
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse().ok();
+            }
+        }
+    }
+
+    let content_length = content_length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header")
+    })?;
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Some(serde_json::from_slice(&body)))
+}
+
+/// Write a single message.
+pub fn write_message<W: Write>(writer: &mut W, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+
+    write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    writer.flush()
+}
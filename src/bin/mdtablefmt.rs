@@ -0,0 +1,421 @@
+//! Command line interface for Markdown Table Formatter.
+//!
+//! With no file arguments, the document is read from stdin and the formatted document is written to stdout.  Otherwise, each file is formatted in place.
+//!
+//! Line endings are never normalized.  Each table keeps the line endings it was written with, and all text outside of tables is copied verbatim.
+
+// the binary needs a file system and stdio, and the library has different signatures on wasm32
+#![cfg_attr(target_arch = "wasm32", no_main)]
+#![cfg(not(target_arch = "wasm32"))]
+
+use markdown_table_formatter::{
+    extract_tables, find_unformatted_tables, format_tables, sort_tables, FormatOptions, SortMethod,
+    SortOptions,
+};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Format the GitHub Flavored Markdown tables in Markdown files.
+
+Usage: mdtablefmt [OPTIONS] [FILE]...
+       mdtablefmt sort (--column <NAME> | --index <N>) [SORT OPTIONS] [FILE]...
+
+With no FILE, read from stdin and write to stdout.  Otherwise, format each FILE in place.
+
+Options:
+      --check    Do not write anything.  List every unformatted table and exit with status 1 if there are any
+      --diff     Do not write anything.  Print a unified diff of the changes formatting would make
+      --stdout   Write formatted files to stdout instead of formatting in place
+  -h, --help     Print help
+  -V, --version  Print version
+
+The sort command sorts the body rows of every table with the chosen column, and formats the sorted tables.
+
+Sort options:
+      --column <NAME>  Sort by the column whose header is NAME
+      --index <N>      Sort by column N, counting from 1
+      --by <METHOD>    Compare cells with METHOD: lexical (default), natural, numeric, or case-insensitive
+      --descending     Sort from largest to smallest
+      --check          Do not write anything.  List every unsorted table and exit with status 1 if there are any
+      --stdout         Write sorted files to stdout instead of sorting in place
+";
+
+/// Parsed command line arguments for formatting.
+#[derive(Debug, Default)]
+struct FormatArgs {
+    files: Vec<PathBuf>,
+    check: bool,
+    diff: bool,
+    stdout: bool,
+}
+
+/// Parsed command line arguments for the `sort` command.
+#[derive(Debug)]
+struct SortArgs {
+    files: Vec<PathBuf>,
+    check: bool,
+    stdout: bool,
+    sort: SortOptions,
+}
+
+#[derive(Debug)]
+enum Command {
+    Help,
+    Version,
+    Format(FormatArgs),
+    Sort(SortArgs),
+}
+
+/// Parse the command line arguments, not including the program name.
+///
+/// A first argument of `sort` selects the `sort` command.  A file named `sort` can still be formatted with `mdtablefmt -- sort`.
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.into_iter().peekable();
+
+    if args.peek().is_some_and(|arg| arg == "sort") {
+        args.next();
+        return parse_sort_args(args);
+    }
+
+    let mut format_args = FormatArgs::default();
+    let mut only_files = false;
+
+    for arg in args {
+        if only_files || !arg.starts_with('-') || arg == "-" {
+            format_args.files.push(PathBuf::from(arg));
+            continue;
+        }
+
+        match arg.as_str() {
+            "--" => only_files = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "--check" => format_args.check = true,
+            "--diff" => format_args.diff = true,
+            "--stdout" => format_args.stdout = true,
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    Ok(Command::Format(format_args))
+}
+
+/// Parse the arguments of the `sort` command, not including the command name.
+fn parse_sort_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut files = Vec::new();
+    let mut check = false;
+    let mut stdout = false;
+    let mut sort: Option<SortOptions> = None;
+    let mut method = SortMethod::default();
+    let mut descending = false;
+    let mut only_files = false;
+
+    while let Some(arg) = args.next() {
+        if only_files || !arg.starts_with('-') || arg == "-" {
+            files.push(PathBuf::from(arg));
+            continue;
+        }
+
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("a value is required for '{arg}'"))
+        };
+
+        match arg.as_str() {
+            "--" => only_files = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "--column" => sort = Some(SortOptions::by_header(&value()?)),
+            "--index" => {
+                let value = value()?;
+                let index = value
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| index.checked_sub(1))
+                    .ok_or_else(|| format!("invalid column number '{value}'"))?;
+
+                sort = Some(SortOptions::by_index(index));
+            }
+            "--by" => {
+                method = match value()?.as_str() {
+                    "lexical" => SortMethod::Lexical,
+                    "natural" => SortMethod::Natural,
+                    "numeric" => SortMethod::Numeric,
+                    "case-insensitive" => SortMethod::CaseInsensitive,
+                    other => return Err(format!("unknown sort method '{other}'")),
+                }
+            }
+            "--descending" => descending = true,
+            "--check" => check = true,
+            "--stdout" => stdout = true,
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    let sort = sort.ok_or("the sort command requires '--column' or '--index'")?;
+
+    Ok(Command::Sort(SortArgs {
+        files,
+        check,
+        stdout,
+        sort: sort.method(method).descending(descending),
+    }))
+}
+
+/// Read a whole file, or stdin if `path` is `-`.
+fn read_input(path: &Path) -> io::Result<String> {
+    if path == Path::new("-") {
+        let mut doc = String::new();
+        io::stdin().read_to_string(&mut doc)?;
+        return Ok(doc);
+    }
+
+    fs::read_to_string(path)
+}
+
+/// Name of `path` to use in messages and diff headers.
+fn display_name(path: &Path) -> String {
+    if path == Path::new("-") {
+        return String::from("<stdin>");
+    }
+
+    path.display().to_string()
+}
+
+/// Split `doc` into lines, keeping the line endings.
+fn split_lines(doc: &str) -> Vec<&str> {
+    doc.split_inclusive('\n').collect()
+}
+
+/// Write one side of a diff line, adding a marker if the line has no trailing newline.
+///
+/// The line keeps its original line ending, so that the diff of a file with `\r\n` line endings still applies with `patch` or `git apply`.
+fn push_diff_line(diff: &mut String, marker: char, line: &str) {
+    diff.push(marker);
+    diff.push_str(line);
+
+    if !line.ends_with('\n') {
+        diff.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+/// Return a unified diff between `original` and `formatted` with three lines of context.
+///
+/// ## Remarks
+///
+/// Formatting only rewrites whitespace inside table rows and never adds or removes a line, so line `n` of `original` always corresponds to line `n` of `formatted`.  This makes a general purpose diff algorithm unnecessary.  If the line counts ever differ, the whole file is treated as a single change.
+fn unified_diff(name: &str, original: &str, formatted: &str) -> String {
+    const CONTEXT: usize = 3;
+
+    let old_lines = split_lines(original);
+    let new_lines = split_lines(formatted);
+
+    let mut diff = format!("--- a/{name}\n+++ b/{name}\n");
+
+    if old_lines.len() != new_lines.len() {
+        diff.push_str(&format!(
+            "@@ -1,{} +1,{} @@\n",
+            old_lines.len(),
+            new_lines.len()
+        ));
+        old_lines
+            .iter()
+            .for_each(|line| push_diff_line(&mut diff, '-', line));
+        new_lines
+            .iter()
+            .for_each(|line| push_diff_line(&mut diff, '+', line));
+
+        return diff;
+    }
+
+    let changed: Vec<usize> = (0..old_lines.len())
+        .filter(|&index| old_lines[index] != new_lines[index])
+        .collect();
+
+    // group changed lines whose context would overlap into the same hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &index in &changed {
+        match hunks.last_mut() {
+            Some((_, end)) if index <= *end + 2 * CONTEXT + 1 => *end = index,
+            _ => hunks.push((index, index)),
+        }
+    }
+
+    for (first_change, last_change) in hunks {
+        let start = first_change.saturating_sub(CONTEXT);
+        let end = (last_change + CONTEXT).min(old_lines.len() - 1);
+        let length = end - start + 1;
+
+        diff.push_str(&format!("@@ -{0},{1} +{0},{1} @@\n", start + 1, length));
+
+        let mut index = start;
+        while index <= end {
+            if old_lines[index] == new_lines[index] {
+                push_diff_line(&mut diff, ' ', old_lines[index]);
+                index += 1;
+                continue;
+            }
+
+            // print each run of changed lines as a block of removals followed by a block of additions
+            let run_start = index;
+            while index <= end && old_lines[index] != new_lines[index] {
+                index += 1;
+            }
+
+            old_lines[run_start..index]
+                .iter()
+                .for_each(|line| push_diff_line(&mut diff, '-', line));
+            new_lines[run_start..index]
+                .iter()
+                .for_each(|line| push_diff_line(&mut diff, '+', line));
+        }
+    }
+
+    diff
+}
+
+/// Format a single file (or stdin).
+///
+/// ## Returns
+///
+/// Whether the file contains unformatted tables, or an error message prefixed with the file name.
+fn format_file(path: &Path, args: &FormatArgs) -> Result<bool, String> {
+    let name = display_name(path);
+    let error = |err: io::Error| format!("{name}: {err}");
+
+    let doc = read_input(path).map_err(error)?;
+    let formatted = format_tables(&doc);
+    let unformatted = formatted != doc;
+
+    if args.check || args.diff {
+        let mut stdout = io::stdout().lock();
+
+        if args.check {
+            for lines in find_unformatted_tables(&doc, &FormatOptions::default()) {
+                writeln!(
+                    stdout,
+                    "{name}:{}-{}: table is not formatted",
+                    lines.start(),
+                    lines.end()
+                )
+                .map_err(error)?;
+            }
+        }
+
+        if args.diff && unformatted {
+            stdout
+                .write_all(unified_diff(&name, &doc, &formatted).as_bytes())
+                .map_err(error)?;
+        }
+
+        return Ok(unformatted);
+    }
+
+    write_output(path, &formatted, unformatted, args.stdout).map_err(error)?;
+
+    Ok(unformatted)
+}
+
+/// Write `new_doc` to stdout if `stdout` is set or `path` is `-`.  Otherwise, write it to `path` if it `changed`.
+fn write_output(path: &Path, new_doc: &str, changed: bool, stdout: bool) -> io::Result<()> {
+    if stdout || path == Path::new("-") {
+        io::stdout().write_all(new_doc.as_bytes())?;
+    } else if changed {
+        fs::write(path, new_doc)?;
+    }
+
+    Ok(())
+}
+
+/// Sort the tables in a single file (or stdin).
+///
+/// ## Returns
+///
+/// Whether the file contains unsorted tables, or an error message prefixed with the file name.
+fn sort_file(path: &Path, args: &SortArgs) -> Result<bool, String> {
+    let name = display_name(path);
+    let error = |err: io::Error| format!("{name}: {err}");
+
+    let doc = read_input(path).map_err(error)?;
+
+    if args.check {
+        let mut unsorted = false;
+        let mut stdout = io::stdout().lock();
+
+        for table in extract_tables(&doc) {
+            let mut sorted = table.clone();
+
+            if sorted.sort(&args.sort) && sorted.rows != table.rows {
+                unsorted = true;
+                writeln!(
+                    stdout,
+                    "{name}:{}-{}: table is not sorted",
+                    table.lines.start(),
+                    table.lines.end()
+                )
+                .map_err(error)?;
+            }
+        }
+
+        return Ok(unsorted);
+    }
+
+    let sorted = sort_tables(&doc, &args.sort);
+    let changed = sorted != doc;
+
+    write_output(path, &sorted, changed, args.stdout).map_err(error)?;
+
+    Ok(changed)
+}
+
+/// Run `process` on every file in `files`, or stdin if there are none.
+///
+/// `process` returns whether the file failed a check.  If `check` is set, any failed check makes the exit status 1.
+fn run<F: Fn(&Path) -> Result<bool, String>>(
+    files: &[PathBuf],
+    check: bool,
+    process: F,
+) -> ExitCode {
+    let files = if files.is_empty() {
+        vec![PathBuf::from("-")]
+    } else {
+        files.to_vec()
+    };
+
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for path in &files {
+        match process(path) {
+            Ok(true) if check => exit_code = ExitCode::FAILURE,
+            Ok(_) => (),
+            Err(message) => {
+                eprintln!("mdtablefmt: {message}");
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    exit_code
+}
+
+fn main() -> ExitCode {
+    match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Help) => {
+            print!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Version) => {
+            println!("mdtablefmt {}", env!("CARGO_PKG_VERSION"));
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Format(args)) => run(&args.files, args.check, |path| format_file(path, &args)),
+        Ok(Command::Sort(args)) => run(&args.files, args.check, |path| sort_file(path, &args)),
+        Err(message) => {
+            eprintln!("mdtablefmt: {message}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
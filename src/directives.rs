@@ -0,0 +1,315 @@
+//! Per-table formatting directives, written in an HTML comment right before a table.
+//!
+//! ```markdown
+//! <!-- table-fmt: sort=Name align=Size:right,Time:decimal min-width=Name:12 compact -->
+//! | Name | Size |
+//! | ---- | ---- |
+//! ```
+//!
+//! Directives only affect the table that follows the comment.  Columns are referred to by the text of their header cell, which can be quoted if it contains whitespace, as in `sort="Error code"`.  Unknown directives and columns that don't exist are ignored.
+//!
+//! Comments can also turn formatting off for a region of the document, or for the next table only.
+//!
+//! ```markdown
+//! <!-- markdown-table-formatter-disable -->
+//! |not|formatted|
+//! |-|-|
+//! <!-- markdown-table-formatter-enable -->
+//!
+//! <!-- markdown-table-formatter-disable-next-table -->
+//! |not|formatted|
+//! |-|-|
+//! ```
+
+use comrak::nodes::TableAlignment;
+
+use crate::options::FormatOptions;
+use crate::sort::{sort_rows, SortColumn, SortMethod, SortOptions};
+use crate::table_formatter::Row;
+
+/// Text that starts the body of a directive comment.
+const DIRECTIVE_PREFIX: &str = "table-fmt:";
+
+/// Return the text inside an HTML block that is a single comment, without surrounding whitespace.
+fn get_comment_body(html: &str) -> Option<&str> {
+    Some(
+        html.trim()
+            .strip_prefix("<!--")?
+            .strip_suffix("-->")?
+            .trim(),
+    )
+}
+
+/// A comment that turns formatting off or on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum IgnoreComment {
+    /// ignore every table until the next `Enable`
+    Disable,
+    Enable,
+    /// ignore the next table only
+    DisableNextTable,
+}
+
+impl IgnoreComment {
+    /// Parse the literal text of an HTML block, returning `None` if it isn't a single ignore comment.
+    pub(crate) fn parse(html: &str) -> Option<IgnoreComment> {
+        match get_comment_body(html)? {
+            "markdown-table-formatter-disable" => Some(IgnoreComment::Disable),
+            "markdown-table-formatter-enable" => Some(IgnoreComment::Enable),
+            "markdown-table-formatter-disable-next-table" => Some(IgnoreComment::DisableNextTable),
+            _ => None,
+        }
+    }
+}
+
+/// Directives parsed from the comment before a table.
+///
+/// - `sort` column and ordering to sort the body rows by
+/// - `alignments` alignment overrides for the delimiter row, by header text
+/// - `decimal_columns` columns aligned on their decimal separator, by header text
+/// - `min_widths` minimum content widths, by header text
+/// - `compact` whether to use the compact style
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Directives {
+    pub(crate) sort: Option<SortOptions>,
+    pub(crate) alignments: Vec<(String, TableAlignment)>,
+    pub(crate) decimal_columns: Vec<String>,
+    pub(crate) min_widths: Vec<(String, usize)>,
+    pub(crate) compact: bool,
+}
+
+/// Split `text` on whitespace, keeping whitespace inside double quotes and removing the quotes.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut in_quotes = false;
+
+    for character in text.chars() {
+        match character {
+            '"' => in_quotes = !in_quotes,
+            _ if character.is_whitespace() && !in_quotes => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            _ => token.push(character),
+        }
+    }
+
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+/// Parse `column[:method][:asc|desc]`, such as `Size:numeric:desc`.
+fn parse_sort(value: &str) -> SortOptions {
+    let mut column = value;
+    let mut method = SortMethod::default();
+    let mut descending = false;
+
+    while let Some((rest, modifier)) = column.rsplit_once(':') {
+        match modifier {
+            "asc" => descending = false,
+            "desc" => descending = true,
+            "lexical" => method = SortMethod::Lexical,
+            "natural" => method = SortMethod::Natural,
+            "numeric" => method = SortMethod::Numeric,
+            "case-insensitive" => method = SortMethod::CaseInsensitive,
+            // part of the column name
+            _ => break,
+        }
+
+        column = rest;
+    }
+
+    SortOptions {
+        column: SortColumn::Header(column.trim().to_string()),
+        method,
+        descending,
+    }
+}
+
+/// Parse a comma separated list of `column:value` pairs, skipping any pair where `parse` fails.
+fn parse_column_values<T, F: Fn(&str) -> Option<T>>(value: &str, parse: F) -> Vec<(String, T)> {
+    value
+        .split(',')
+        .filter_map(|item| {
+            let (column, value) = item.rsplit_once(':')?;
+
+            Some((column.trim().to_string(), parse(value.trim())?))
+        })
+        .collect()
+}
+
+fn parse_alignment(value: &str) -> Option<TableAlignment> {
+    match value {
+        "none" => Some(TableAlignment::None),
+        "left" => Some(TableAlignment::Left),
+        "center" => Some(TableAlignment::Center),
+        "right" => Some(TableAlignment::Right),
+        _ => None,
+    }
+}
+
+impl Directives {
+    /// Parse the literal text of an HTML block, returning `None` if it isn't a single `table-fmt:` comment.
+    pub(crate) fn parse(html: &str) -> Option<Directives> {
+        let body = get_comment_body(html)?.strip_prefix(DIRECTIVE_PREFIX)?;
+
+        let mut directives = Directives::default();
+
+        for token in tokenize(body) {
+            match token.split_once('=') {
+                Some(("sort", value)) => directives.sort = Some(parse_sort(value)),
+                Some(("align", value)) => {
+                    directives
+                        .alignments
+                        .append(&mut parse_column_values(value, parse_alignment));
+                    directives.decimal_columns.extend(
+                        parse_column_values(value, |value| (value == "decimal").then_some(()))
+                            .into_iter()
+                            .map(|(column, _)| column),
+                    );
+                }
+                Some(("min-width", value)) => directives
+                    .min_widths
+                    .append(&mut parse_column_values(value, |width| width.parse().ok())),
+                Some(("style", "compact")) => directives.compact = true,
+                None if token == "compact" => directives.compact = true,
+                _ => (),
+            }
+        }
+
+        Some(directives)
+    }
+
+    /// Apply the directives to the content rows and alignments of a table.
+    ///
+    /// ## Parameters
+    ///
+    /// - `content_rows` all rows of the table except for the delimiter row.  Body rows are sorted in place.
+    /// - `alignments` alignments parsed from the delimiter row, which are overridden in place
+    /// - `options` the options the table would otherwise be rendered with
+    ///
+    /// ## Returns
+    ///
+    /// The options to render this table with, and the index each body row had before sorting, in the new order of the body rows.
+    pub(crate) fn apply(
+        &self,
+        content_rows: &mut [Row],
+        alignments: &mut [TableAlignment],
+        options: &FormatOptions,
+    ) -> (FormatOptions, Vec<usize>) {
+        let mut options = options.clone();
+
+        let Some((header, body)) = content_rows.split_first_mut() else {
+            return (options, Vec::new());
+        };
+
+        let mut order: Vec<usize> = (0..body.len()).collect();
+
+        let column_count = alignments.len();
+        let column_index = |name: &str| -> Option<usize> {
+            header
+                .iter()
+                .take(column_count)
+                .position(|cell| cell.content == name)
+        };
+
+        for (name, alignment) in &self.alignments {
+            if let Some(index) = column_index(name) {
+                alignments[index] = *alignment;
+            }
+        }
+
+        for name in &self.decimal_columns {
+            if let Some(index) = column_index(name) {
+                options.decimal_columns.push(index);
+            }
+        }
+
+        for (name, width) in &self.min_widths {
+            if let Some(index) = column_index(name) {
+                options.min_column_widths.resize(column_count, 0);
+                options.min_column_widths[index] = options.min_column_widths[index].max(*width);
+            }
+        }
+
+        if let Some(sort) = &self.sort {
+            if let Some(index) = sort.column_index(header, column_count) {
+                order = sort_rows(body, index, sort.method, sort.descending);
+            }
+        }
+
+        options.compact |= self.compact;
+
+        (options, order)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("<!-- table-fmt: compact -->", true; "Flag")]
+    #[test_case("<!--table-fmt: style=compact-->\n", true; "Style")]
+    #[test_case("<!-- table-fmt: sort=Name -->", false; "Not compact")]
+    fn test_parse_compact(html: &str, correct: bool) {
+        assert_eq!(Directives::parse(html).unwrap().compact, correct);
+    }
+
+    #[test_case("<!-- markdown-table-formatter-disable -->", Some(IgnoreComment::Disable); "Disable")]
+    #[test_case("<!--markdown-table-formatter-enable-->\n", Some(IgnoreComment::Enable); "Enable")]
+    #[test_case("<!-- markdown-table-formatter-disable-next-table -->", Some(IgnoreComment::DisableNextTable); "Disable next table")]
+    #[test_case("<!-- markdown-table-formatter-disable --> text", None; "Trailing text")]
+    fn test_parse_ignore_comment(html: &str, correct: Option<IgnoreComment>) {
+        assert_eq!(IgnoreComment::parse(html), correct);
+    }
+
+    #[test_case("<!-- a comment -->"; "Other comment")]
+    #[test_case("<div>table-fmt: compact</div>"; "Not a comment")]
+    fn test_parse_not_directive(html: &str) {
+        assert_eq!(Directives::parse(html), None);
+    }
+
+    #[test]
+    fn test_parse_directives() {
+        let directives = Directives::parse(
+            "<!-- table-fmt: sort=\"Error code\":natural:desc align=Size:right,Name:center,Bad:up min-width=Name:12 unknown=1 -->",
+        )
+        .unwrap();
+
+        assert_eq!(
+            directives.sort,
+            Some(
+                SortOptions::by_header("Error code")
+                    .method(SortMethod::Natural)
+                    .descending(true)
+            )
+        );
+        assert_eq!(
+            directives.alignments,
+            vec![
+                (String::from("Size"), TableAlignment::Right),
+                (String::from("Name"), TableAlignment::Center)
+            ]
+        );
+        assert_eq!(directives.min_widths, vec![(String::from("Name"), 12)]);
+        assert!(directives.decimal_columns.is_empty());
+    }
+
+    #[test]
+    fn test_parse_decimal_alignment() {
+        let directives =
+            Directives::parse("<!-- table-fmt: align=Time:decimal,Name:left -->").unwrap();
+
+        assert_eq!(directives.decimal_columns, vec![String::from("Time")]);
+        assert_eq!(
+            directives.alignments,
+            vec![(String::from("Name"), TableAlignment::Left)]
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Describe formatting as a list of text replacements instead of a whole new document.
+//!
+//! Editors apply these edits directly, which keeps undo history, folding, and markers outside of tables intact.
+
+use std::ops::Range;
+
+use crate::ast::get_tables;
+use crate::options::FormatOptions;
+use crate::table_formatter::{format_table, Selection};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+/// Replace the text between `start` and `end` with `new_text`.
+///
+/// ## Values
+///
+/// - `start` and `end` byte offsets into the UTF-8 document
+/// - `utf16_start` and `utf16_end` the same offsets counted in UTF-16 code units, which is how JavaScript strings and VS Code count
+/// - `new_text`
+///
+/// All offsets refer to the original document, so edits can be applied together by any editor API that accepts multiple non-overlapping edits.  To apply them one at a time, apply them in reverse order.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter_with_clone))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+    pub start: usize,
+    pub end: usize,
+    pub utf16_start: usize,
+    pub utf16_end: usize,
+    pub new_text: String,
+}
+
+impl TextEdit {
+    /// Byte offsets of the replaced text.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// UTF-16 code unit offsets of the replaced text.
+    pub fn utf16_range(&self) -> Range<usize> {
+        self.utf16_start..self.utf16_end
+    }
+}
+
+/// Convert increasing byte offsets into UTF-16 code unit offsets without rescanning the document for each offset.
+pub(crate) struct Utf16Counter<'a> {
+    doc: &'a str,
+    byte_offset: usize,
+    utf16_offset: usize,
+}
+
+impl<'a> Utf16Counter<'a> {
+    pub(crate) fn new(doc: &'a str) -> Self {
+        Utf16Counter {
+            doc,
+            byte_offset: 0,
+            utf16_offset: 0,
+        }
+    }
+
+    /// Return the UTF-16 offset of `byte_offset`.
+    ///
+    /// `byte_offset` must be on a character boundary and must not be less than the offset of the previous call.
+    pub(crate) fn offset(&mut self, byte_offset: usize) -> usize {
+        self.utf16_offset += self.doc[self.byte_offset..byte_offset]
+            .encode_utf16()
+            .count();
+        self.byte_offset = byte_offset;
+
+        self.utf16_offset
+    }
+}
+
+/// Return one edit for each table in `selection` that formatting would change.
+pub fn get_edits<T: AsRef<str>>(
+    doc: T,
+    selection: &Selection,
+    options: &FormatOptions,
+) -> Vec<TextEdit> {
+    let doc: &str = doc.as_ref();
+    let mut counter = Utf16Counter::new(doc);
+
+    let mut edits = Vec::new();
+
+    for table in get_tables(doc)
+        .iter()
+        .filter(|table| selection.contains(table))
+    {
+        let formatted = format_table(table, options);
+
+        if formatted == table.text {
+            continue;
+        }
+
+        edits.push(TextEdit {
+            start: table.range.start,
+            end: table.range.end,
+            utf16_start: counter.offset(table.range.start),
+            utf16_end: counter.offset(table.range.end),
+            new_text: formatted,
+        });
+    }
+
+    edits
+}
@@ -1,9 +1,29 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
 mod ast;
+mod directives;
+mod edits;
+mod lint;
+mod offsets;
+mod operations;
+mod options;
+mod skeleton;
+mod sort;
+mod table;
 mod table_formatter;
 
-use table_formatter::format;
+use edits::get_edits;
+pub use edits::TextEdit;
+pub use lint::{Diagnostic, Position, Rule, Severity};
+pub use operations::TableOperation;
+pub use options::{DelimiterStyle, FormatOptions, HeaderAlignment};
+pub use skeleton::TableSkeleton;
+pub use sort::{SortMethod, SortOptions};
+pub use table::{Alignment, Table};
+use table_formatter::{format, format_selection, get_unformatted_tables, Selection};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::ops::{Range, RangeInclusive};
 
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
@@ -11,12 +31,424 @@ use wasm_bindgen::prelude::*;
 /// Format the GitHub Flavored Markdown tables in the `doc` string.
 #[cfg(not(target_arch = "wasm32"))]
 pub fn format_tables<T: AsRef<str>>(doc: T) -> String {
-    format(doc)
+    format(doc, &FormatOptions::default())
 }
 
 /// Format the GitHub Flavored Markdown tables in the `doc` string.
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 pub fn format_tables(doc: String) -> String {
-    format(doc)
+    format(doc, &FormatOptions::default())
+}
+
+/// Format the GitHub Flavored Markdown tables in the `doc` string using the style in `options`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn format_tables_with<T: AsRef<str>>(doc: T, options: &FormatOptions) -> String {
+    format(doc, options)
+}
+
+/// Format the GitHub Flavored Markdown tables in the `doc` string using the style in `options`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn format_tables_with(doc: String, options: &FormatOptions) -> String {
+    format(doc, options)
+}
+
+/// Format only the GitHub Flavored Markdown tables in the `doc` string that overlap the byte offsets in `range`.
+///
+/// Every byte outside of those tables is left untouched.  An empty `range`, such as a cursor position, selects the table it is in.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn format_tables_in_range<T: AsRef<str>>(doc: T, range: Range<usize>) -> String {
+    format_selection(doc, &Selection::Bytes(range), &FormatOptions::default())
+}
+
+/// Format only the GitHub Flavored Markdown tables in the `doc` string that overlap the byte offsets from `start` to `end`.
+///
+/// Every byte outside of those tables is left untouched.  An empty range, such as a cursor position, selects the table it is in.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn format_tables_in_range(doc: String, start: usize, end: usize) -> String {
+    format_selection(
+        doc,
+        &Selection::Bytes(start..end),
+        &FormatOptions::default(),
+    )
+}
+
+/// Format only the GitHub Flavored Markdown tables in the `doc` string that overlap the byte offsets in `range` using the style in `options`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn format_tables_in_range_with<T: AsRef<str>>(
+    doc: T,
+    range: Range<usize>,
+    options: &FormatOptions,
+) -> String {
+    format_selection(doc, &Selection::Bytes(range), options)
+}
+
+/// Format only the GitHub Flavored Markdown tables in the `doc` string that overlap the byte offsets from `start` to `end` using the style in `options`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn format_tables_in_range_with(
+    doc: String,
+    start: usize,
+    end: usize,
+    options: &FormatOptions,
+) -> String {
+    format_selection(doc, &Selection::Bytes(start..end), options)
+}
+
+/// Format only the GitHub Flavored Markdown tables in the `doc` string that overlap the 1-indexed, inclusive range of `lines`.
+///
+/// Every byte outside of those tables is left untouched.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn format_tables_in_lines<T: AsRef<str>>(doc: T, lines: RangeInclusive<usize>) -> String {
+    format_selection(doc, &Selection::Lines(lines), &FormatOptions::default())
+}
+
+/// Format only the GitHub Flavored Markdown tables in the `doc` string that overlap the 1-indexed lines from `start_line` to `end_line`, inclusive.
+///
+/// Every byte outside of those tables is left untouched.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn format_tables_in_lines(doc: String, start_line: usize, end_line: usize) -> String {
+    format_selection(
+        doc,
+        &Selection::Lines(start_line..=end_line),
+        &FormatOptions::default(),
+    )
+}
+
+/// Format only the GitHub Flavored Markdown tables in the `doc` string that overlap the 1-indexed, inclusive range of `lines` using the style in `options`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn format_tables_in_lines_with<T: AsRef<str>>(
+    doc: T,
+    lines: RangeInclusive<usize>,
+    options: &FormatOptions,
+) -> String {
+    format_selection(doc, &Selection::Lines(lines), options)
+}
+
+/// Format only the GitHub Flavored Markdown tables in the `doc` string that overlap the 1-indexed lines from `start_line` to `end_line`, inclusive, using the style in `options`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn format_tables_in_lines_with(
+    doc: String,
+    start_line: usize,
+    end_line: usize,
+    options: &FormatOptions,
+) -> String {
+    format_selection(doc, &Selection::Lines(start_line..=end_line), options)
+}
+
+/// Return one `TextEdit` for each GitHub Flavored Markdown table in the `doc` string that `format_tables` would change.
+///
+/// Applying every edit to `doc` gives the same result as `format_tables`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn format_edits<T: AsRef<str>>(doc: T) -> Vec<TextEdit> {
+    get_edits(doc, &Selection::All, &FormatOptions::default())
+}
+
+/// Return one `TextEdit` for each GitHub Flavored Markdown table in the `doc` string that `format_tables` would change.
+///
+/// Applying every edit to `doc` gives the same result as `format_tables`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn format_edits(doc: String) -> Vec<TextEdit> {
+    get_edits(doc, &Selection::All, &FormatOptions::default())
+}
+
+/// Return one `TextEdit` for each GitHub Flavored Markdown table in the `doc` string that `format_tables_with` would change.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn format_edits_with<T: AsRef<str>>(doc: T, options: &FormatOptions) -> Vec<TextEdit> {
+    get_edits(doc, &Selection::All, options)
+}
+
+/// Return one `TextEdit` for each GitHub Flavored Markdown table in the `doc` string that `format_tables_with` would change.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn format_edits_with(doc: String, options: &FormatOptions) -> Vec<TextEdit> {
+    get_edits(doc, &Selection::All, options)
+}
+
+/// Return one `TextEdit` for each GitHub Flavored Markdown table in the `doc` string that overlaps the byte offsets in `range` and that `format_tables_in_range` would change.
+///
+/// An empty `range`, such as a cursor position, selects the table it is in.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn format_edits_in_range<T: AsRef<str>>(doc: T, range: Range<usize>) -> Vec<TextEdit> {
+    get_edits(doc, &Selection::Bytes(range), &FormatOptions::default())
+}
+
+/// Return one `TextEdit` for each GitHub Flavored Markdown table in the `doc` string that overlaps the byte offsets from `start` to `end` and that `format_tables_in_range` would change.
+///
+/// An empty range, such as a cursor position, selects the table it is in.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn format_edits_in_range(doc: String, start: usize, end: usize) -> Vec<TextEdit> {
+    get_edits(
+        doc,
+        &Selection::Bytes(start..end),
+        &FormatOptions::default(),
+    )
+}
+
+/// Return one `TextEdit` for each GitHub Flavored Markdown table in the `doc` string that overlaps the byte offsets in `range` and that `format_tables_in_range_with` would change.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn format_edits_in_range_with<T: AsRef<str>>(
+    doc: T,
+    range: Range<usize>,
+    options: &FormatOptions,
+) -> Vec<TextEdit> {
+    get_edits(doc, &Selection::Bytes(range), options)
+}
+
+/// Return one `TextEdit` for each GitHub Flavored Markdown table in the `doc` string that overlaps the byte offsets from `start` to `end` and that `format_tables_in_range_with` would change.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn format_edits_in_range_with(
+    doc: String,
+    start: usize,
+    end: usize,
+    options: &FormatOptions,
+) -> Vec<TextEdit> {
+    get_edits(doc, &Selection::Bytes(start..end), options)
+}
+
+/// Return the byte offsets in `format_tables(doc)` that match the byte `offsets` in `doc`.
+///
+/// An offset inside a table stays in the same cell at the same place in the cell content, so a cursor doesn't jump when a document is formatted while typing.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn map_offsets<T: AsRef<str>>(doc: T, offsets: &[usize]) -> Vec<usize> {
+    offsets::map(doc, offsets, &FormatOptions::default())
+}
+
+/// Return the byte offsets in `format_tables(doc)` that match the byte `offsets` in `doc`.
+///
+/// An offset inside a table stays in the same cell at the same place in the cell content, so a cursor doesn't jump when a document is formatted while typing.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn map_offsets(doc: String, offsets: Vec<usize>) -> Vec<usize> {
+    offsets::map(doc, &offsets, &FormatOptions::default())
+}
+
+/// Return the byte offsets in `format_tables_with(doc, options)` that match the byte `offsets` in `doc`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn map_offsets_with<T: AsRef<str>>(
+    doc: T,
+    offsets: &[usize],
+    options: &FormatOptions,
+) -> Vec<usize> {
+    offsets::map(doc, offsets, options)
+}
+
+/// Return the byte offsets in `format_tables_with(doc, options)` that match the byte `offsets` in `doc`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn map_offsets_with(doc: String, offsets: Vec<usize>, options: &FormatOptions) -> Vec<usize> {
+    offsets::map(doc, &offsets, options)
+}
+
+/// Return the UTF-16 code unit offsets in `format_tables_with(doc, options)` that match the UTF-16 code unit `offsets` in `doc`.
+fn map_utf16(doc: &str, offsets: &[usize], options: &FormatOptions) -> Vec<usize> {
+    let byte_offsets = offsets::utf16_to_byte_offsets(doc, offsets);
+    let mapped = offsets::map(doc, &byte_offsets, options);
+
+    offsets::byte_to_utf16_offsets(&format(doc, options), &mapped)
+}
+
+/// Return the UTF-16 code unit offsets in `format_tables(doc)` that match the UTF-16 code unit `offsets` in `doc`.
+///
+/// This is `map_offsets` for editors that count in UTF-16 code units, such as VS Code.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn map_utf16_offsets<T: AsRef<str>>(doc: T, offsets: &[usize]) -> Vec<usize> {
+    map_utf16(doc.as_ref(), offsets, &FormatOptions::default())
+}
+
+/// Return the UTF-16 code unit offsets in `format_tables(doc)` that match the UTF-16 code unit `offsets` in `doc`.
+///
+/// This is `map_offsets` for editors that count in UTF-16 code units, such as VS Code.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn map_utf16_offsets(doc: String, offsets: Vec<usize>) -> Vec<usize> {
+    map_utf16(&doc, &offsets, &FormatOptions::default())
+}
+
+/// Return the UTF-16 code unit offsets in `format_tables_with(doc, options)` that match the UTF-16 code unit `offsets` in `doc`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn map_utf16_offsets_with<T: AsRef<str>>(
+    doc: T,
+    offsets: &[usize],
+    options: &FormatOptions,
+) -> Vec<usize> {
+    map_utf16(doc.as_ref(), offsets, options)
+}
+
+/// Return the UTF-16 code unit offsets in `format_tables_with(doc, options)` that match the UTF-16 code unit `offsets` in `doc`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn map_utf16_offsets_with(
+    doc: String,
+    offsets: Vec<usize>,
+    options: &FormatOptions,
+) -> Vec<usize> {
+    map_utf16(&doc, &offsets, options)
+}
+
+/// Turn line `line` (1-indexed) of the `doc` string into the header row of a new table.
+///
+/// The line is formatted as a header row and followed by a generated delimiter row and an empty line for the first body row, where the cursor is placed.  Returns `None` if the line isn't a paragraph line with at least one vertical bar, or is already part of a table.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn create_table_skeleton<T: AsRef<str>>(doc: T, line: usize) -> Option<TableSkeleton> {
+    skeleton::create(doc, line, &FormatOptions::default())
+}
+
+/// Turn line `line` (1-indexed) of the `doc` string into the header row of a new table.
+///
+/// The line is formatted as a header row and followed by a generated delimiter row and an empty line for the first body row, where the cursor is placed.  Returns `undefined` if the line isn't a paragraph line with at least one vertical bar, or is already part of a table.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn create_table_skeleton(doc: String, line: usize) -> Option<TableSkeleton> {
+    skeleton::create(doc, line, &FormatOptions::default())
+}
+
+/// Turn line `line` (1-indexed) of the `doc` string into the header row of a new table using the style in `options`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn create_table_skeleton_with<T: AsRef<str>>(
+    doc: T,
+    line: usize,
+    options: &FormatOptions,
+) -> Option<TableSkeleton> {
+    skeleton::create(doc, line, options)
+}
+
+/// Turn line `line` (1-indexed) of the `doc` string into the header row of a new table using the style in `options`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn create_table_skeleton_with(
+    doc: String,
+    line: usize,
+    options: &FormatOptions,
+) -> Option<TableSkeleton> {
+    skeleton::create(doc, line, options)
+}
+
+/// Apply `operation` to the table containing the byte `offset` in the `doc` string, such as inserting a column to the left of the cell at `offset`.
+///
+/// Returns an edit replacing the whole table with the changed and formatted table, or `None` if `offset` isn't in a table or the operation doesn't apply there.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn edit_table<T: AsRef<str>>(
+    doc: T,
+    offset: usize,
+    operation: TableOperation,
+) -> Option<TextEdit> {
+    operations::edit(doc, offset, operation, &FormatOptions::default())
+}
+
+/// Apply `operation` to the table containing the byte `offset` in the `doc` string, such as inserting a column to the left of the cell at `offset`.
+///
+/// Returns an edit replacing the whole table with the changed and formatted table, or `undefined` if `offset` isn't in a table or the operation doesn't apply there.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn edit_table(doc: String, offset: usize, operation: TableOperation) -> Option<TextEdit> {
+    operations::edit(doc, offset, operation, &FormatOptions::default())
+}
+
+/// Apply `operation` to the table containing the byte `offset` in the `doc` string and format it using the style in `options`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn edit_table_with<T: AsRef<str>>(
+    doc: T,
+    offset: usize,
+    operation: TableOperation,
+    options: &FormatOptions,
+) -> Option<TextEdit> {
+    operations::edit(doc, offset, operation, options)
+}
+
+/// Apply `operation` to the table containing the byte `offset` in the `doc` string and format it using the style in `options`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn edit_table_with(
+    doc: String,
+    offset: usize,
+    operation: TableOperation,
+    options: &FormatOptions,
+) -> Option<TextEdit> {
+    operations::edit(doc, offset, operation, options)
+}
+
+/// Sort the body rows of every GitHub Flavored Markdown table in the `doc` string that has the column in `sort`.
+///
+/// Sorted tables are also formatted.  Tables without the column are left untouched.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn sort_tables<T: AsRef<str>>(doc: T, sort: &SortOptions) -> String {
+    sort::sort(doc, sort, &FormatOptions::default())
+}
+
+/// Sort the body rows of every GitHub Flavored Markdown table in the `doc` string that has the column in `sort`.
+///
+/// Sorted tables are also formatted.  Tables without the column are left untouched.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn sort_tables(doc: String, sort: &SortOptions) -> String {
+    sort::sort(doc, sort, &FormatOptions::default())
+}
+
+/// Sort the body rows of every GitHub Flavored Markdown table in the `doc` string that has the column in `sort`, formatting sorted tables using the style in `options`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn sort_tables_with<T: AsRef<str>>(
+    doc: T,
+    sort: &SortOptions,
+    options: &FormatOptions,
+) -> String {
+    sort::sort(doc, sort, options)
+}
+
+/// Sort the body rows of every GitHub Flavored Markdown table in the `doc` string that has the column in `sort`, formatting sorted tables using the style in `options`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn sort_tables_with(doc: String, sort: &SortOptions, options: &FormatOptions) -> String {
+    sort::sort(doc, sort, options)
+}
+
+/// Return every GitHub Flavored Markdown table in the `doc` string as a structured `Table`.
+///
+/// Replacing `table.range` with `table.to_markdown(&FormatOptions::default())` for every table gives the same result as `format_tables`.
+pub fn extract_tables<T: AsRef<str>>(doc: T) -> Vec<Table> {
+    table::extract(doc)
+}
+
+/// Return a diagnostic for every malformed row or cell in the GitHub Flavored Markdown tables in the `doc` string.
+///
+/// The formatter never deletes text from malformed tables, so this is the only way to find out about them.
+pub fn lint_tables<T: AsRef<str>>(doc: T) -> Vec<Diagnostic> {
+    lint::lint(doc)
+}
+
+/// Return a diagnostic for every change that formatting the GitHub Flavored Markdown tables in the `doc` string with `FormatOptions::repair` would make.
+///
+/// Each diagnostic is on the row that is changed, and has the rule of the problem the change fixes.  Rows that get empty cells have `Rule::MissingCells`, and a delimiter row that gets more cells has `Rule::ExtraCells`.
+pub fn find_repairs<T: AsRef<str>>(doc: T) -> Vec<Diagnostic> {
+    lint::repairs(doc)
+}
+
+/// Return the 1-indexed, inclusive line ranges of every table in `doc` that is not formatted according to `options`.
+///
+/// An empty result means that `format_tables_with(doc, options)` would not change `doc`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn find_unformatted_tables<T: AsRef<str>>(
+    doc: T,
+    options: &FormatOptions,
+) -> Vec<RangeInclusive<usize>> {
+    get_unformatted_tables(doc, options)
+}
+
+/// Return the 1-indexed, inclusive line ranges of every table in `doc` that is not formatted according to `options`.
+///
+/// The ranges are flattened into `[start, end, start, end, ...]`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn find_unformatted_tables(doc: String, options: &FormatOptions) -> Vec<usize> {
+    get_unformatted_tables(doc, options)
+        .into_iter()
+        .flat_map(|lines| [*lines.start(), *lines.end()])
+        .collect()
 }
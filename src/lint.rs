@@ -0,0 +1,272 @@
+//! Report malformed tables without changing them.
+//!
+//! The formatter tolerates malformed rows and never deletes text (see `table_formatter_behavior.md`), so problems such as cells that will not be rendered are otherwise invisible.  This module reports each of those problems as a `Diagnostic`.
+
+use std::ops::Range;
+
+use crate::ast::{get_tables, TableInDocument};
+use crate::table_formatter::{
+    get_cell_ranges, parse_row_text, INDENTATION_CHARS, INDEX_OF_DELIMITER_ROW,
+};
+
+/// Return the range of `text` from the first vertical bar or content to the end of the content, skipping container indentation and surrounding whitespace.
+fn get_row_range(text: &str) -> Range<usize> {
+    let row_start = text.len() - text.trim_start_matches(INDENTATION_CHARS).len();
+
+    row_start..text.trim_end().len().max(row_start)
+}
+
+/// How serious a diagnostic is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Information,
+}
+
+/// A lint rule.
+///
+/// Every rule has a stable identifier returned by `Rule::id` that is safe to match on in other tools.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// A row has more cells than the delimiter row.  The extra cells are not rendered.
+    ExtraCells,
+    /// A row has fewer cells than the delimiter row.  The missing cells are rendered empty.
+    MissingCells,
+    /// A header cell has no content.
+    EmptyHeaderCell,
+    /// A row has no vertical bars.  This is often a line of text that was meant to be a paragraph after the table.
+    RowWithoutPipes,
+}
+
+impl Rule {
+    /// Return the stable identifier of the rule.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::ExtraCells => "extra-cells",
+            Rule::MissingCells => "missing-cells",
+            Rule::EmptyHeaderCell => "empty-header-cell",
+            Rule::RowWithoutPipes => "row-without-pipes",
+        }
+    }
+
+    /// Return the severity diagnostics of this rule are reported with.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Rule::ExtraCells => Severity::Warning,
+            Rule::MissingCells => Severity::Warning,
+            Rule::EmptyHeaderCell => Severity::Information,
+            Rule::RowWithoutPipes => Severity::Warning,
+        }
+    }
+}
+
+/// A location in a document.
+///
+/// Both values are 1-indexed.  `column` counts UTF-8 bytes from the start of the line, the same way `comrak` does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A problem found in a table.
+///
+/// ## Values
+///
+/// - `range` byte offsets of the problem in the document
+/// - `start` position of the first byte of the problem
+/// - `end` position immediately after the last byte of the problem
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub rule: Rule,
+    pub severity: Severity,
+    pub message: String,
+    pub range: Range<usize>,
+    pub start: Position,
+    pub end: Position,
+}
+
+/// A single line of a table along with where it is in the document.
+struct TableLine<'a> {
+    /// text of the line without the line ending or any text preceding the table on its first line
+    text: &'a str,
+    /// 1-indexed line number
+    line: usize,
+    /// byte offset of the start of `text` in the document
+    offset: usize,
+    /// byte offset of the start of `text` in its line
+    column_offset: usize,
+}
+
+impl TableLine<'_> {
+    fn diagnostic(&self, rule: Rule, message: String, range: Range<usize>) -> Diagnostic {
+        Diagnostic {
+            rule,
+            severity: rule.severity(),
+            message,
+            range: self.offset + range.start..self.offset + range.end,
+            start: Position {
+                line: self.line,
+                column: self.column_offset + range.start + 1,
+            },
+            end: Position {
+                line: self.line,
+                column: self.column_offset + range.end + 1,
+            },
+        }
+    }
+}
+
+/// Return every line of `table` along with its position in the document.
+fn get_table_lines<'a>(table: &TableInDocument<'a>) -> Vec<TableLine<'a>> {
+    let mut offset = table.range.start;
+    let mut lines = Vec::new();
+
+    for (index, line) in table.text.split_inclusive('\n').enumerate() {
+        let line_length = line.len();
+        let line = line.trim_end_matches('\n').trim_end_matches('\r');
+
+        let column_offset = match index {
+            0 => table.first_line_offset.min(line.len()),
+            _ => 0,
+        };
+
+        // the column `comrak` reports isn't always on a character boundary of this line
+        let (column_offset, text) = match line.get(column_offset..) {
+            Some(text) => (column_offset, text),
+            None => (0, line),
+        };
+
+        lines.push(TableLine {
+            text,
+            line: table.lines.start() + index,
+            offset: offset + column_offset,
+            column_offset,
+        });
+
+        offset += line_length;
+    }
+
+    lines
+}
+
+/// Return every diagnostic for a single table.
+fn lint_table(table: &TableInDocument<'_>) -> Vec<Diagnostic> {
+    let column_count = table.alignments.len();
+    let mut diagnostics = Vec::new();
+
+    for (index, line) in get_table_lines(table).iter().enumerate() {
+        if index == INDEX_OF_DELIMITER_ROW {
+            continue;
+        }
+
+        let cell_ranges = get_cell_ranges(line.text);
+
+        if index == 0 {
+            for (range, cell) in std::iter::zip(&cell_ranges, parse_row_text(line.text)) {
+                if cell.content.is_empty() {
+                    diagnostics.push(line.diagnostic(
+                        Rule::EmptyHeaderCell,
+                        String::from("header cell is empty"),
+                        range.clone(),
+                    ));
+                }
+            }
+        }
+
+        let row_range = get_row_range(line.text);
+
+        if !line.text.contains('|') {
+            diagnostics.push(line.diagnostic(
+                Rule::RowWithoutPipes,
+                String::from("row has no vertical bars, so it is part of the table above it"),
+                row_range,
+            ));
+            continue;
+        }
+
+        if cell_ranges.len() > column_count {
+            let extra = cell_ranges.len() - column_count;
+            let range = cell_ranges[column_count].start..cell_ranges[cell_ranges.len() - 1].end;
+
+            diagnostics.push(line.diagnostic(
+                Rule::ExtraCells,
+                format!("row has {extra} more cell(s) than the delimiter row, which will not be rendered"),
+                range,
+            ));
+        } else if cell_ranges.len() < column_count {
+            let missing = column_count - cell_ranges.len();
+
+            diagnostics.push(line.diagnostic(
+                Rule::MissingCells,
+                format!("row has {missing} fewer cell(s) than the delimiter row"),
+                row_range,
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// Return a diagnostic for every change `FormatOptions::repair` makes to a single table.
+///
+/// The diagnostics have the rule of the problem each change fixes.
+fn repair_table(table: &TableInDocument<'_>) -> Vec<Diagnostic> {
+    let delimiter_column_count = table.alignments.len();
+    let lines = get_table_lines(table);
+
+    let cell_counts: Vec<usize> = lines
+        .iter()
+        .map(|line| get_cell_ranges(line.text).len())
+        .collect();
+
+    let column_count = cell_counts
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != INDEX_OF_DELIMITER_ROW)
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0)
+        .max(delimiter_column_count);
+
+    let mut diagnostics = Vec::new();
+
+    for (index, (line, cell_count)) in std::iter::zip(&lines, cell_counts).enumerate() {
+        let (rule, message) = match index {
+            INDEX_OF_DELIMITER_ROW if column_count > delimiter_column_count => (
+                Rule::ExtraCells,
+                format!(
+                    "added {} delimiter cell(s) so that every cell is rendered",
+                    column_count - delimiter_column_count
+                ),
+            ),
+            INDEX_OF_DELIMITER_ROW => continue,
+            _ if column_count > cell_count => (
+                Rule::MissingCells,
+                format!("added {} empty cell(s)", column_count - cell_count),
+            ),
+            _ => continue,
+        };
+
+        diagnostics.push(line.diagnostic(rule, message, get_row_range(line.text)));
+    }
+
+    diagnostics
+}
+
+/// Return a diagnostic for every change `FormatOptions::repair` makes to the tables in `doc`, ordered by position.
+pub fn repairs<T: AsRef<str>>(doc: T) -> Vec<Diagnostic> {
+    get_tables(doc.as_ref())
+        .iter()
+        .flat_map(repair_table)
+        .collect()
+}
+
+/// Return diagnostics for every malformed table in `doc`, ordered by position.
+pub fn lint<T: AsRef<str>>(doc: T) -> Vec<Diagnostic> {
+    get_tables(doc.as_ref())
+        .iter()
+        .flat_map(lint_table)
+        .collect()
+}
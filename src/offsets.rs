@@ -0,0 +1,241 @@
+//! Map positions in a document to the matching positions after formatting.
+//!
+//! Formatting only changes whitespace inside tables, so a position outside of a table only moves by the change in length of the tables before it.  A position inside a table follows the cell it is in.  It stays at the same place in the cell content, even though the whitespace around the content changes.
+
+use std::ops::Range;
+
+use crate::ast::{get_tables, TableInDocument};
+use crate::options::FormatOptions;
+use crate::table_formatter::{
+    format_table_with_row_order, get_cell_ranges, INDEX_OF_DELIMITER_ROW,
+};
+
+/// Return the byte offset of the start of each line in `text`, along with the line without its line ending.
+fn get_lines(text: &str) -> Vec<(usize, &str)> {
+    let mut offset = 0;
+
+    text.split_inclusive('\n')
+        .map(|line| {
+            let start = offset;
+            offset += line.len();
+
+            (start, line.trim_end_matches('\n').trim_end_matches('\r'))
+        })
+        .collect()
+}
+
+/// Return the start and end of the content of the cell at `range` in `line`, not including surrounding whitespace.
+///
+/// The content of an empty cell is placed after the first space of padding, where text typed into the cell would go.
+fn get_content_range(line: &str, range: &Range<usize>) -> Range<usize> {
+    let cell = &line[range.clone()];
+    if cell.trim().is_empty() {
+        let start = (range.start + 1).min(range.end);
+        return start..start;
+    }
+
+    let start = range.start + cell.len() - cell.trim_start().len();
+
+    start..(range.start + cell.trim_end().len()).max(start)
+}
+
+/// Map `column`, a byte offset in `line`, to the matching byte offset in `formatted_line`.
+///
+/// ## Parameters
+///
+/// - `line` a row of the original table without its line ending
+/// - `formatted_line` the same row after formatting
+/// - `prefix_length` length of the text preceding the table on this line, which is the same in both lines
+/// - `column`
+fn map_column(line: &str, formatted_line: &str, prefix_length: usize, column: usize) -> usize {
+    if column < prefix_length {
+        return column;
+    }
+
+    let shift = |ranges: Vec<Range<usize>>| -> Vec<Range<usize>> {
+        ranges
+            .into_iter()
+            .map(|range| range.start + prefix_length..range.end + prefix_length)
+            .collect()
+    };
+
+    let cells = shift(get_cell_ranges(&line[prefix_length..]));
+    let formatted_cells = shift(get_cell_ranges(&formatted_line[prefix_length..]));
+
+    let (Some(first), Some(last)) = (cells.first(), cells.last()) else {
+        return column.min(formatted_line.len());
+    };
+    let (Some(formatted_first), Some(formatted_last)) =
+        (formatted_cells.first(), formatted_cells.last())
+    else {
+        return column.min(formatted_line.len());
+    };
+
+    // before the first cell, such as on a leading vertical bar
+    if column < first.start {
+        let distance = first.start - column;
+        return formatted_first
+            .start
+            .saturating_sub(distance)
+            .max(prefix_length);
+    }
+
+    // after the last cell, such as on a trailing vertical bar
+    if column > last.end {
+        let distance = column - last.end;
+        return (formatted_last.end + distance).min(formatted_line.len());
+    }
+
+    let Some(index) = cells.iter().position(|range| column <= range.end) else {
+        return column.min(formatted_line.len());
+    };
+    let Some(formatted_range) = formatted_cells.get(index) else {
+        return formatted_line.len();
+    };
+
+    let content = get_content_range(line, &cells[index]);
+    let formatted_content = get_content_range(formatted_line, formatted_range);
+
+    if column <= content.start {
+        formatted_content.start
+    } else if column >= content.end {
+        formatted_content.end
+    } else {
+        (formatted_content.start + column - content.start).min(formatted_content.end)
+    }
+}
+
+/// Map `offset`, a byte offset in `table.text`, to the matching byte offset in `formatted`.
+///
+/// `order` is the index each body row had in `table`, in the order of the body rows of `formatted`, so that an offset follows its row when a directive sorts the table.
+fn map_table_offset(
+    table: &TableInDocument<'_>,
+    formatted: &str,
+    order: &[usize],
+    offset: usize,
+) -> usize {
+    let lines = get_lines(table.text);
+    let formatted_lines = get_lines(formatted);
+
+    let row = lines
+        .iter()
+        .rposition(|(start, _)| *start <= offset)
+        .unwrap_or(0);
+
+    let body_start = INDEX_OF_DELIMITER_ROW + 1;
+    let formatted_row = match row.checked_sub(body_start) {
+        Some(body_row) => order
+            .iter()
+            .position(|&index| index == body_row)
+            .map_or(row, |position| position + body_start),
+        None => row,
+    };
+
+    let Some((formatted_start, formatted_line)) = formatted_lines.get(formatted_row) else {
+        return formatted.len();
+    };
+
+    let (start, line) = lines[row];
+    let prefix_length = match row {
+        0 => table.first_line_offset.min(line.len()),
+        _ => 0,
+    };
+
+    // the column `comrak` reports isn't always on a character boundary of either line
+    let prefix_length = match line.is_char_boundary(prefix_length)
+        && formatted_line.is_char_boundary(prefix_length)
+    {
+        true => prefix_length,
+        false => 0,
+    };
+
+    let column = (offset - start).min(line.len());
+
+    formatted_start + map_column(line, formatted_line, prefix_length, column)
+}
+
+/// Return the offsets in the formatted document that match `offsets` in `doc`.
+///
+/// Offsets past the end of `doc` are treated as the end of `doc`, and offsets inside a UTF-8 character are treated as the start of that character.
+pub fn map<T: AsRef<str>>(doc: T, offsets: &[usize], options: &FormatOptions) -> Vec<usize> {
+    let doc: &str = doc.as_ref();
+
+    // each table, its formatted text, the order of its formatted body rows, and the change in length of every table before it
+    let mut tables: Vec<(TableInDocument<'_>, String, Vec<usize>, isize)> = Vec::new();
+    let mut shift: isize = 0;
+
+    for table in get_tables(doc) {
+        let (formatted, order) = format_table_with_row_order(&table, options);
+        let length_change = formatted.len() as isize - table.text.len() as isize;
+
+        tables.push((table, formatted, order, shift));
+        shift += length_change;
+    }
+
+    offsets
+        .iter()
+        .map(|&offset| {
+            let offset = floor_char_boundary(doc, offset);
+
+            let index = tables.partition_point(|(table, _, _, _)| table.range.start <= offset);
+
+            match index.checked_sub(1).map(|index| &tables[index]) {
+                Some((table, formatted, order, shift)) if offset <= table.range.end => {
+                    let formatted_start = (table.range.start as isize + shift) as usize;
+
+                    formatted_start
+                        + map_table_offset(table, formatted, order, offset - table.range.start)
+                }
+                Some((table, formatted, _, shift)) => {
+                    let length_change = formatted.len() as isize - table.text.len() as isize;
+
+                    (offset as isize + shift + length_change) as usize
+                }
+                None => offset,
+            }
+        })
+        .collect()
+}
+
+/// Convert UTF-16 code unit `offsets` in `doc` to byte offsets.
+///
+/// Offsets past the end of `doc` are treated as the end of `doc`.
+pub fn utf16_to_byte_offsets(doc: &str, offsets: &[usize]) -> Vec<usize> {
+    offsets
+        .iter()
+        .map(|&offset| {
+            let mut utf16_count = 0;
+
+            for (index, character) in doc.char_indices() {
+                if utf16_count >= offset {
+                    return index;
+                }
+                utf16_count += character.len_utf16();
+            }
+
+            doc.len()
+        })
+        .collect()
+}
+
+/// Return the largest character boundary in `doc` that is not greater than `offset`.
+fn floor_char_boundary(doc: &str, offset: usize) -> usize {
+    let mut offset = offset.min(doc.len());
+    while !doc.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    offset
+}
+
+/// Convert byte `offsets` in `doc` to UTF-16 code unit offsets.
+pub fn byte_to_utf16_offsets(doc: &str, offsets: &[usize]) -> Vec<usize> {
+    offsets
+        .iter()
+        .map(|&offset| {
+            doc[..floor_char_boundary(doc, offset)]
+                .encode_utf16()
+                .count()
+        })
+        .collect()
+}
@@ -0,0 +1,168 @@
+//! Editing commands for the table under a cursor, such as "insert column left" or "move row up".
+//!
+//! Each command parses the table into a `Table`, changes it, and renders it again, so the delimiter row and the alignments always stay consistent with the cells.
+
+use crate::ast::{get_tables, TableInDocument};
+use crate::edits::{TextEdit, Utf16Counter};
+use crate::options::FormatOptions;
+use crate::table::{Alignment, Table};
+use crate::table_formatter::{get_cell_ranges, INDEX_OF_DELIMITER_ROW};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+/// A change to the table under a cursor.
+///
+/// New rows and columns are empty, and new columns have no alignment.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TableOperation {
+    InsertRowAbove,
+    InsertRowBelow,
+    DeleteRow,
+    MoveRowUp,
+    MoveRowDown,
+    InsertColumnLeft,
+    InsertColumnRight,
+    DeleteColumn,
+    MoveColumnLeft,
+    MoveColumnRight,
+}
+
+/// A row of a table.
+enum RowPosition {
+    Header,
+    Delimiter,
+    /// index into `Table::rows`
+    Body(usize),
+}
+
+/// Return the row and the 0-indexed column of the cell at `offset`, a byte offset in `table.text`.
+///
+/// An offset before the first cell of a row is in the first cell, and an offset after the last cell is in the last cell.
+fn get_cell_position(table: &TableInDocument<'_>, offset: usize) -> (RowPosition, usize) {
+    let mut line_start = 0;
+    let mut line_index = 0;
+
+    for (index, line) in table.text.split_inclusive('\n').enumerate() {
+        line_index = index;
+
+        // the last line has no line ending, and the end of the table is on it
+        if offset < line_start + line.len() || !line.ends_with('\n') {
+            break;
+        }
+
+        line_start += line.len();
+    }
+
+    let line = table.text[line_start..].lines().next().unwrap_or_default();
+    let prefix_length = match line_index {
+        0 => table.first_line_offset.min(line.len()),
+        _ => 0,
+    };
+
+    // the column `comrak` reports isn't always on a character boundary of this line
+    let (prefix_length, row_text) = match line.get(prefix_length..) {
+        Some(row_text) => (prefix_length, row_text),
+        None => (0, line),
+    };
+
+    let column = (offset - line_start).saturating_sub(prefix_length);
+    let ranges = get_cell_ranges(row_text);
+    let cell = ranges
+        .iter()
+        .position(|range| column <= range.end)
+        .unwrap_or(ranges.len().saturating_sub(1));
+
+    let row = match line_index {
+        0 => RowPosition::Header,
+        INDEX_OF_DELIMITER_ROW => RowPosition::Delimiter,
+        index => RowPosition::Body(index - INDEX_OF_DELIMITER_ROW - 1),
+    };
+
+    (row, cell)
+}
+
+/// Apply `operation` to `table` at `row` and `column`.
+///
+/// ## Returns
+///
+/// Whether the operation applies.  For example, the header row cannot be deleted or moved, the last body row cannot move down, and a table must keep at least one column.
+fn apply(table: &mut Table, operation: TableOperation, row: RowPosition, column: usize) -> bool {
+    let column_count = table.column_count();
+    let row_count = table.rows.len();
+
+    // extra cells after the last column defined by the delimiter row are not part of any column
+    let in_column = column < column_count;
+
+    match (operation, row) {
+        (TableOperation::InsertRowAbove, RowPosition::Body(index)) => table.insert_row(index),
+        (TableOperation::InsertRowBelow, RowPosition::Header | RowPosition::Delimiter) => {
+            table.insert_row(0)
+        }
+        (TableOperation::InsertRowBelow, RowPosition::Body(index)) => table.insert_row(index + 1),
+        (TableOperation::DeleteRow, RowPosition::Body(index)) => {
+            table.delete_row(index);
+        }
+        (TableOperation::MoveRowUp, RowPosition::Body(index)) if index > 0 => {
+            table.move_row(index, index - 1)
+        }
+        (TableOperation::MoveRowDown, RowPosition::Body(index)) if index + 1 < row_count => {
+            table.move_row(index, index + 1)
+        }
+        (TableOperation::InsertColumnLeft, _) if in_column => {
+            table.insert_column(column, Alignment::None)
+        }
+        (TableOperation::InsertColumnRight, _) if in_column => {
+            table.insert_column(column + 1, Alignment::None)
+        }
+        (TableOperation::DeleteColumn, _) if in_column && column_count > 1 => {
+            table.delete_column(column)
+        }
+        (TableOperation::MoveColumnLeft, _) if in_column && column > 0 => {
+            table.move_column(column, column - 1)
+        }
+        (TableOperation::MoveColumnRight, _) if column + 1 < column_count => {
+            table.move_column(column, column + 1)
+        }
+        _ => return false,
+    }
+
+    true
+}
+
+/// Apply `operation` to the table containing the byte `offset` in `doc`.
+///
+/// ## Returns
+///
+/// An edit replacing the table with the changed table rendered in the style of `options`, or `None` if `offset` is not in a table or the operation doesn't apply to the cell at `offset`.
+pub fn edit<T: AsRef<str>>(
+    doc: T,
+    offset: usize,
+    operation: TableOperation,
+    options: &FormatOptions,
+) -> Option<TextEdit> {
+    let doc: &str = doc.as_ref();
+
+    let tables = get_tables(doc);
+    let table = tables
+        .iter()
+        .find(|table| table.range.start <= offset && offset <= table.range.end)?;
+
+    let (row, column) = get_cell_position(table, offset - table.range.start);
+
+    let mut edited = Table::from(table);
+    if !apply(&mut edited, operation, row, column) {
+        return None;
+    }
+
+    let mut counter = Utf16Counter::new(doc);
+
+    Some(TextEdit {
+        start: table.range.start,
+        end: table.range.end,
+        utf16_start: counter.offset(table.range.start),
+        utf16_end: counter.offset(table.range.end),
+        new_text: edited.to_markdown(options),
+    })
+}
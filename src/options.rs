@@ -0,0 +1,250 @@
+//! User-configurable formatting style.
+//!
+//! `FormatOptions::default()` produces exactly the same output as `format_tables`, so every option is opt-in.
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+/// How delimiter cells are written.  Examples are of a left aligned column with the default padding.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DelimiterStyle {
+    /// Padded like every other cell, with hyphens filling the width of the column, as in `| :---- |`.
+    #[default]
+    Padded,
+    /// No padding and a fixed number of hyphens, set by `min_delimiter_dashes`, as in `|:---|`.  The delimiter row is not aligned with the other rows, and doesn't change the width of any column.
+    Minimal,
+    /// No padding, with hyphens filling the width of the column including its padding, as in `|:------|`.
+    Filled,
+}
+
+/// How the cells of the header row are aligned in the source.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HeaderAlignment {
+    /// The same as the body cells of its column, as set by the delimiter row.
+    #[default]
+    Column,
+    Left,
+    Center,
+    Right,
+}
+
+/// Style options for formatting GitHub Flavored Markdown tables.
+///
+/// Options are set with builder-style methods that consume and return the options.
+///
+/// ```rust
+/// use markdown_table_formatter::{format_tables_with, FormatOptions};
+///
+/// let options = FormatOptions::new().min_delimiter_dashes(1);
+///
+/// assert_eq!(format_tables_with("|A|B|\n|-|-|", &options), "| A | B |\n| - | - |");
+/// ```
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    pub(crate) min_delimiter_dashes: usize,
+    /// minimum content width of each column, set by `min-width` directives
+    pub(crate) min_column_widths: Vec<usize>,
+    pub(crate) compact: bool,
+    pub(crate) outer_pipes: bool,
+    pub(crate) cell_padding: usize,
+    pub(crate) delimiter_style: DelimiterStyle,
+    pub(crate) align_extra_columns: bool,
+    pub(crate) repair: bool,
+    /// 0-indexed columns whose cells are aligned on their decimal separator
+    pub(crate) decimal_columns: Vec<usize>,
+    pub(crate) infer_alignment: bool,
+    pub(crate) header_alignment: HeaderAlignment,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            min_delimiter_dashes: 3,
+            min_column_widths: Vec::new(),
+            compact: false,
+            outer_pipes: true,
+            cell_padding: 1,
+            delimiter_style: DelimiterStyle::Padded,
+            align_extra_columns: false,
+            repair: false,
+            decimal_columns: Vec::new(),
+            infer_alignment: false,
+            header_alignment: HeaderAlignment::Column,
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+impl FormatOptions {
+    /// Create options with the default style.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(constructor))]
+    pub fn new() -> FormatOptions {
+        FormatOptions::default()
+    }
+
+    /// Set the minimum number of hyphens in each delimiter cell, not counting alignment colons.
+    ///
+    /// The default is 3.  The GFM specification requires at least one hyphen, so `0` is treated as `1`.
+    pub fn min_delimiter_dashes(mut self, dashes: usize) -> FormatOptions {
+        self.min_delimiter_dashes = dashes.max(1);
+        self
+    }
+
+    /// Use the compact style, which has no padding around cells and a single hyphen in each delimiter cell.
+    ///
+    /// ```markdown
+    /// |Name|Size|
+    /// |:-|-:|
+    /// |a.txt|12|
+    /// ```
+    ///
+    /// Columns are not aligned, so `min_delimiter_dashes` has no effect.  Alignment colons and escaped vertical bars are always kept.  The default is `false`.
+    pub fn compact(mut self, compact: bool) -> FormatOptions {
+        self.compact = compact;
+        self
+    }
+
+    /// Set whether each row starts and ends with a vertical bar.
+    ///
+    /// ```markdown
+    /// Name  | Size
+    /// :---- | ---:
+    /// a.txt |   12
+    /// ```
+    ///
+    /// Cells stay aligned either way.  Some tables can't be written without outer vertical bars, such as a table with a single column, a row with an empty first or last cell, a first cell that would start a list, heading, or other block, or a row that would start with a space, such as when the first column is right aligned or centered.  Those tables always keep them.  The default is `true`.
+    pub fn outer_pipes(mut self, outer_pipes: bool) -> FormatOptions {
+        self.outer_pipes = outer_pipes;
+        self
+    }
+
+    /// Set the number of spaces on each side of the content of every cell, including delimiter cells.
+    ///
+    /// ```rust
+    /// use markdown_table_formatter::{format_tables_with, FormatOptions};
+    ///
+    /// let options = FormatOptions::new().cell_padding(2);
+    ///
+    /// assert_eq!(format_tables_with("|A|B|\n|-|-|", &options), "|  A    |  B    |\n|  ---  |  ---  |");
+    /// ```
+    ///
+    /// Columns stay aligned for any padding, including `0`.  The compact style never has padding, so this has no effect with `compact`.  The default is 1.
+    pub fn cell_padding(mut self, padding: usize) -> FormatOptions {
+        self.cell_padding = padding;
+        self
+    }
+
+    /// Set how delimiter cells are written.
+    ///
+    /// ```rust
+    /// use markdown_table_formatter::{format_tables_with, DelimiterStyle, FormatOptions};
+    ///
+    /// let table = "|Name|Size|\n|:-|-:|";
+    ///
+    /// let options = FormatOptions::new().delimiter_style(DelimiterStyle::Minimal);
+    /// assert_eq!(format_tables_with(table, &options), "| Name | Size |\n|:---|---:|");
+    ///
+    /// let options = FormatOptions::new().delimiter_style(DelimiterStyle::Filled);
+    /// assert_eq!(format_tables_with(table, &options), "| Name | Size |\n|:-----|-----:|");
+    /// ```
+    ///
+    /// The compact style has its own delimiter cells, so this has no effect with `compact`.  The default is `DelimiterStyle::Padded`.
+    pub fn delimiter_style(mut self, style: DelimiterStyle) -> FormatOptions {
+        self.delimiter_style = style;
+        self
+    }
+
+    /// Set whether cells past the last column of the delimiter row are aligned into columns of their own.
+    ///
+    /// ```markdown
+    /// | 1    | 2    |
+    /// | :--- | :--- |
+    /// | 1    | 2    | 3   |
+    /// | 1    | 2    | 333 |
+    /// ```
+    ///
+    /// Extra columns are not rendered, and they have no delimiter cell, so they are aligned as if the delimiter cell had no colons.  By default, extra cells keep a single space of padding and are not aligned.  See `table_formatter_behavior.md`.  The default is `false`.
+    pub fn align_extra_columns(mut self, align: bool) -> FormatOptions {
+        self.align_extra_columns = align;
+        self
+    }
+
+    /// Set whether malformed tables are repaired, so that every cell is rendered.
+    ///
+    /// ```rust
+    /// use markdown_table_formatter::{format_tables_with, FormatOptions};
+    ///
+    /// let options = FormatOptions::new().repair(true);
+    ///
+    /// assert_eq!(
+    ///     format_tables_with("|A|B|\n|-|-|\n|C|\n|D|E|F|", &options),
+    ///     "| A   | B   |     |\n| --- | --- | --- |\n| C   |     |     |\n| D   | E   | F   |"
+    /// );
+    /// ```
+    ///
+    /// A row with fewer cells than the widest row gets empty cells at the end.  If a row is wider than the delimiter row, the delimiter row gets `---` cells so that the extra cells are rendered.  Use `find_repairs` to list the changes this makes.  The default is `false`.
+    pub fn repair(mut self, repair: bool) -> FormatOptions {
+        self.repair = repair;
+        self
+    }
+
+    /// Line up the decimal separators of the body cells in the column at the 0-indexed `column`.  Call this once for each column.
+    ///
+    /// ```rust
+    /// use markdown_table_formatter::{format_tables_with, FormatOptions};
+    ///
+    /// let options = FormatOptions::new().decimal_alignment(1);
+    ///
+    /// assert_eq!(
+    ///     format_tables_with("|Run|Time|\n|-|-:|\n|a|1.5|\n|b|12.25|\n|c|100|", &options),
+    ///     "| Run |   Time |\n| --- | -----: |\n| a   |   1.5  |\n| b   |  12.25 |\n| c   | 100    |"
+    /// );
+    /// ```
+    ///
+    /// The decimal separator is the first `.` in a cell.  A cell without one, such as an integer, ends where its separator would be.  GFM has no decimal alignment, so the column keeps a right alignment in the delimiter row, and any other alignment is replaced by no alignment.  This has no effect with `compact`.
+    pub fn decimal_alignment(mut self, column: usize) -> FormatOptions {
+        self.decimal_columns.push(column);
+        self
+    }
+
+    /// Set whether columns without an alignment are right aligned when every body cell is a number, a percentage, or an amount of currency.
+    ///
+    /// ```rust
+    /// use markdown_table_formatter::{format_tables_with, FormatOptions};
+    ///
+    /// let options = FormatOptions::new().infer_alignment(true);
+    ///
+    /// assert_eq!(
+    ///     format_tables_with("|Name|Price|Share|\n|-|-|:-|\n|a|$1,200|5%|\n|b|$3.50|95%|", &options),
+    ///     "| Name |  Price | Share |\n| ---- | -----: | :---- |\n| a    | $1,200 | 5%    |\n| b    |  $3.50 | 95%   |"
+    /// );
+    /// ```
+    ///
+    /// Empty cells are skipped, so a column needs at least one number.  Columns with a colon in their delimiter cell never change.  Directives that set an alignment take precedence.  The default is `false`.
+    pub fn infer_alignment(mut self, infer: bool) -> FormatOptions {
+        self.infer_alignment = infer;
+        self
+    }
+
+    /// Set how the cells of the header row are aligned in the source, regardless of the alignment of their columns.
+    ///
+    /// ```rust
+    /// use markdown_table_formatter::{format_tables_with, FormatOptions, HeaderAlignment};
+    ///
+    /// let options = FormatOptions::new().header_alignment(HeaderAlignment::Center);
+    ///
+    /// assert_eq!(
+    ///     format_tables_with("|Name|Size|\n|:-|-:|\n|a.txt|1,024|", &options),
+    ///     "| Name  | Size  |\n| :---- | ----: |\n| a.txt | 1,024 |"
+    /// );
+    /// ```
+    ///
+    /// Only whitespace in the header row changes.  The delimiter row keeps the alignment of each column, so rendered tables look the same.  This has no effect with `compact`.  The default is `HeaderAlignment::Column`.
+    pub fn header_alignment(mut self, alignment: HeaderAlignment) -> FormatOptions {
+        self.header_alignment = alignment;
+        self
+    }
+}
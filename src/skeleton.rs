@@ -0,0 +1,143 @@
+//! Turn a line of text separated by vertical bars into the start of a new table.
+//!
+//! A GFM table needs a delimiter row before it is recognized as a table, which makes tables tedious to start by hand.  Typing `Name | Age | City` and asking for a skeleton gives a formatted header row, a generated delimiter row, and an empty line to type the first body row on.
+
+use comrak::nodes::TableAlignment;
+
+use crate::ast::{get_paragraphs, get_tables};
+use crate::edits::Utf16Counter;
+use crate::options::FormatOptions;
+use crate::table_formatter::{
+    get_cell_ranges, get_line_indentation, parse_row_text, render_table, TableLayout,
+    INDENTATION_CHARS,
+};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+/// A document with a new table skeleton and where to place the cursor.
+///
+/// ## Values
+///
+/// - `text` the whole document after the header row is turned into a table
+/// - `cursor` byte offset of the start of the line for the first body row, after any indentation
+/// - `utf16_cursor` the same offset counted in UTF-16 code units
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter_with_clone))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableSkeleton {
+    pub text: String,
+    pub cursor: usize,
+    pub utf16_cursor: usize,
+}
+
+/// Return whether `row` has at least one vertical bar that is not escaped.
+fn has_cell_separator(row: &str) -> bool {
+    let ranges = get_cell_ranges(row);
+
+    match ranges.as_slice() {
+        [] => false,
+        [range] => row[range.clone()] != *row.trim_matches(INDENTATION_CHARS),
+        _ => true,
+    }
+}
+
+/// Return the indentation of every line after the first line of a container block whose first line starts with `prefix`.
+///
+/// List item markers and footnote labels are replaced with spaces, and blockquote markers are kept.
+fn get_continuation(prefix: &str) -> String {
+    prefix
+        .chars()
+        .map(|character| match character {
+            '>' | '\t' => character,
+            _ => ' ',
+        })
+        .collect()
+}
+
+/// Turn line `line` of `doc` into the header row of a new table.
+///
+/// ## Parameters
+///
+/// - `doc`
+/// - `line` 1-indexed line number of the header row
+/// - `options`
+///
+/// ## Returns
+///
+/// `None` unless the line is part of a paragraph, is not already part of a table, and has at least one unescaped vertical bar and one non-empty cell.
+///
+/// ## Remarks
+///
+/// The delimiter row has one cell without alignment for each header cell, generated by `get_normalized_delimiter_row` like the delimiter row of any formatted table.  A new line is always inserted after the delimiter row for the first body row, so editors that call this when Enter is pressed should not insert a line break of their own.
+pub fn create<T: AsRef<str>>(
+    doc: T,
+    line: usize,
+    options: &FormatOptions,
+) -> Option<TableSkeleton> {
+    let doc: &str = doc.as_ref();
+
+    if get_tables(doc)
+        .iter()
+        .any(|table| table.lines.contains(&line))
+    {
+        return None;
+    }
+
+    let (paragraph_lines, paragraph_offset) = get_paragraphs(doc)
+        .into_iter()
+        .find(|(lines, _)| lines.contains(&line))?;
+
+    let lines: Vec<&str> = doc.split_inclusive('\n').collect();
+    let line_start: usize = lines[..line - 1].iter().map(|text| text.len()).sum();
+
+    let full_line = lines[line - 1];
+    let text = full_line.trim_end_matches('\n').trim_end_matches('\r');
+    let line_ending = match &full_line[text.len()..] {
+        "\r\n" => "\r\n",
+        _ => "\n",
+    };
+
+    let prefix = match *paragraph_lines.start() == line {
+        true => text.get(..paragraph_offset).unwrap_or_default(),
+        false => get_line_indentation(text),
+    };
+    let row = &text[prefix.len()..];
+
+    let cells = parse_row_text(row);
+    if !has_cell_separator(row) || cells.iter().all(|cell| cell.content.is_empty()) {
+        return None;
+    }
+
+    let continuation = get_continuation(prefix);
+    let layout = TableLayout {
+        indentation: match prefix.is_empty() {
+            true => None,
+            false => Some((prefix.to_string(), continuation.clone())),
+        },
+        line_ending,
+    };
+
+    let alignments = vec![TableAlignment::None; cells.len()];
+    let table = render_table(&[cells], &alignments, &layout, options);
+
+    let rest = match line_start + full_line.len() == doc.len() {
+        // the header row is the last line, so there is nothing after the new line
+        true => "",
+        false => &doc[line_start + text.len()..],
+    };
+
+    let mut new_doc = String::with_capacity(doc.len() + table.len());
+    new_doc.push_str(&doc[..line_start]);
+    new_doc.push_str(&table);
+    new_doc.push_str(line_ending);
+    new_doc.push_str(&continuation);
+
+    let cursor = new_doc.len();
+    new_doc.push_str(rest);
+
+    Some(TableSkeleton {
+        utf16_cursor: Utf16Counter::new(&new_doc).offset(cursor),
+        text: new_doc,
+        cursor,
+    })
+}
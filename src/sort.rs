@@ -0,0 +1,316 @@
+//! Sort the body rows of a table by the cells in one column.
+//!
+//! The header row and delimiter row never move.  Sorting is stable, so rows with equal cells keep their order, and sorting an already sorted table changes nothing.
+
+use std::cmp::Ordering;
+
+use crate::ast::get_tables;
+use crate::options::FormatOptions;
+use crate::table::Table;
+use crate::table_formatter::parse_numeric;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+/// How to compare the cells of the sorted column.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SortMethod {
+    /// Compare Unicode scalar values, so `B` comes before `a` and `10` comes before `9`.
+    #[default]
+    Lexical,
+    /// Compare runs of digits by their numeric value and everything else lexically, so `file9` comes before `file10`.
+    Natural,
+    /// Compare cells as decimal numbers, which can have thousands separators, a percent sign, or a currency symbol, as in `-1,234.5`, `12%`, or `$3.50`.  Cells that aren't numbers, including empty cells, come after every number in both ascending and descending order and are compared lexically.
+    Numeric,
+    /// Compare lowercase text, so `a` and `A` are equal.
+    CaseInsensitive,
+}
+
+/// The column to sort by.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum SortColumn {
+    /// 0-indexed column number
+    Index(usize),
+    /// text of the header cell
+    Header(String),
+}
+
+/// Which column to sort the body rows of a table by, and how.
+///
+/// ```rust
+/// use markdown_table_formatter::{sort_tables, SortMethod, SortOptions};
+///
+/// let options = SortOptions::by_header("Size").method(SortMethod::Numeric).descending(true);
+///
+/// assert_eq!(
+///     sort_tables("|Name|Size|\n|-|-|\n|a|9|\n|b|10|", &options),
+///     "| Name | Size |\n| ---- | ---- |\n| b    | 10   |\n| a    | 9    |"
+/// );
+/// ```
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SortOptions {
+    pub(crate) column: SortColumn,
+    pub(crate) method: SortMethod,
+    pub(crate) descending: bool,
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+impl SortOptions {
+    /// Sort by the column at the 0-indexed `index`, lexically and ascending.
+    pub fn by_index(index: usize) -> SortOptions {
+        SortOptions {
+            column: SortColumn::Index(index),
+            method: SortMethod::default(),
+            descending: false,
+        }
+    }
+
+    /// Sort by the column whose header cell is exactly `header`, lexically and ascending.
+    ///
+    /// If more than one header cell matches, the first one is used.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn by_header(header: &str) -> SortOptions {
+        SortOptions {
+            column: SortColumn::Header(header.trim().to_string()),
+            method: SortMethod::default(),
+            descending: false,
+        }
+    }
+
+    /// Sort by the column whose header cell is exactly `header`, lexically and ascending.
+    ///
+    /// If more than one header cell matches, the first one is used.
+    #[cfg(target_arch = "wasm32")]
+    pub fn by_header(header: String) -> SortOptions {
+        SortOptions {
+            column: SortColumn::Header(header.trim().to_string()),
+            method: SortMethod::default(),
+            descending: false,
+        }
+    }
+
+    /// Set how cells are compared.
+    pub fn method(mut self, method: SortMethod) -> SortOptions {
+        self.method = method;
+        self
+    }
+
+    /// Sort from largest to smallest instead of smallest to largest.
+    ///
+    /// Rows with equal cells keep their order in both directions.
+    pub fn descending(mut self, descending: bool) -> SortOptions {
+        self.descending = descending;
+        self
+    }
+}
+
+impl SortOptions {
+    /// Return the 0-indexed column to sort by in a table with `headers` and `column_count` columns, if the table has that column.
+    pub(crate) fn column_index<T: AsRef<str>>(
+        &self,
+        headers: &[T],
+        column_count: usize,
+    ) -> Option<usize> {
+        let index = match &self.column {
+            SortColumn::Index(index) => *index,
+            SortColumn::Header(header) => headers
+                .iter()
+                .position(|cell| cell.as_ref().trim() == header)?,
+        };
+
+        (index < column_count).then_some(index)
+    }
+}
+
+/// Split `text` into alternating runs of ASCII digits and other characters.
+fn natural_chunks(text: &str) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    for (index, character) in text.char_indices().skip(1) {
+        let previous_is_digit = text[..index]
+            .chars()
+            .next_back()
+            .is_some_and(|previous| previous.is_ascii_digit());
+
+        if previous_is_digit != character.is_ascii_digit() {
+            chunks.push(&text[start..index]);
+            start = index;
+        }
+    }
+
+    if start < text.len() {
+        chunks.push(&text[start..]);
+    }
+
+    chunks
+}
+
+/// Compare `a` and `b` so that runs of digits are ordered by their numeric value.
+fn compare_natural(a: &str, b: &str) -> Ordering {
+    let a_chunks = natural_chunks(a);
+    let b_chunks = natural_chunks(b);
+
+    for (a_chunk, b_chunk) in a_chunks.iter().zip(&b_chunks) {
+        let both_digits = a_chunk.starts_with(|c: char| c.is_ascii_digit())
+            && b_chunk.starts_with(|c: char| c.is_ascii_digit());
+
+        let ordering = match both_digits {
+            true => {
+                // compare digit runs of any length without parsing them, leading zeros break ties
+                let a_value = a_chunk.trim_start_matches('0');
+                let b_value = b_chunk.trim_start_matches('0');
+
+                a_value
+                    .len()
+                    .cmp(&b_value.len())
+                    .then_with(|| a_value.cmp(b_value))
+                    .then_with(|| a_chunk.len().cmp(&b_chunk.len()))
+            }
+            false => a_chunk.cmp(b_chunk),
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    a_chunks.len().cmp(&b_chunks.len())
+}
+
+/// Compare `a` and `b` as decimal numbers, placing anything that isn't a number last, even when `descending`.
+fn compare_numeric(a: &str, b: &str, descending: bool) -> Ordering {
+    let ordering = match (parse_numeric(a), parse_numeric(b)) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => return Ordering::Less,
+        (None, Some(_)) => return Ordering::Greater,
+        (None, None) => a.cmp(b),
+    };
+
+    match descending {
+        true => ordering.reverse(),
+        false => ordering,
+    }
+}
+
+/// Compare two cells using `method`.
+fn compare_cells(a: &str, b: &str, method: SortMethod, descending: bool) -> Ordering {
+    let ordering = match method {
+        SortMethod::Lexical => a.cmp(b),
+        SortMethod::Natural => compare_natural(a, b),
+        SortMethod::Numeric => return compare_numeric(a, b, descending),
+        SortMethod::CaseInsensitive => a.to_lowercase().cmp(&b.to_lowercase()),
+    };
+
+    match descending {
+        true => ordering.reverse(),
+        false => ordering,
+    }
+}
+
+/// Stable sort of `rows` by the cells in `column`.  A row without a cell in `column` sorts as if the cell was empty.
+///
+/// ## Parameters
+///
+/// - `rows` body rows of a table, such as those returned by `parse_row_text`, not including the header row
+/// - `column` 0-indexed column
+/// - `method`
+/// - `descending`
+///
+/// ## Returns
+///
+/// The index each row had before sorting, in the new order of the rows.
+pub(crate) fn sort_rows<T: AsRef<str>>(
+    rows: &mut [Vec<T>],
+    column: usize,
+    method: SortMethod,
+    descending: bool,
+) -> Vec<usize> {
+    let cell = |row: &Vec<T>| -> String {
+        row.get(column)
+            .map(|cell| cell.as_ref().trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let cells: Vec<String> = rows.iter().map(cell).collect();
+
+    let mut order: Vec<usize> = (0..rows.len()).collect();
+    order.sort_by(|&a, &b| compare_cells(&cells[a], &cells[b], method, descending));
+
+    let mut sorted: Vec<Vec<T>> = order
+        .iter()
+        .map(|&index| std::mem::take(&mut rows[index]))
+        .collect();
+    rows.swap_with_slice(&mut sorted);
+
+    order
+}
+
+/// Sort the body rows of every table in `doc` that has the column in `sort`.
+///
+/// Sorted tables are rendered in the style of `options`.  Tables without the column are left untouched.
+pub fn sort<T: AsRef<str>>(doc: T, sort: &SortOptions, options: &FormatOptions) -> String {
+    let doc: &str = doc.as_ref();
+
+    let mut sorted = doc.to_string();
+
+    for table in get_tables(doc).iter().rev() {
+        let mut table = Table::from(table);
+
+        if table.sort(sort) {
+            sorted.replace_range(table.range.clone(), &table.to_markdown(options));
+        }
+    }
+
+    sorted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table_formatter::parse_row_text;
+    use test_case::test_case;
+
+    #[test_case("file9", "file10", Ordering::Less)]
+    #[test_case("file10", "file9", Ordering::Greater)]
+    #[test_case("a2b", "a2c", Ordering::Less)]
+    #[test_case("007", "7", Ordering::Greater)]
+    #[test_case("12345678901234567890", "9", Ordering::Greater)]
+    #[test_case("abc", "abc1", Ordering::Less)]
+    #[test_case("", "0", Ordering::Less)]
+    fn test_compare_natural(a: &str, b: &str, correct: Ordering) {
+        assert_eq!(compare_natural(a, b), correct);
+    }
+
+    #[test]
+    fn test_sort_parsed_rows() {
+        let mut rows: Vec<_> = ["| b | 2 |", "| a |", "| c | 10 |", "| d | 2 |"]
+            .into_iter()
+            .map(parse_row_text)
+            .collect();
+
+        let order = sort_rows(&mut rows, 1, SortMethod::Natural, false);
+
+        assert_eq!(order, vec![1, 0, 3, 2]);
+        let first_cells: Vec<&str> = rows.iter().map(|row| row[0].as_ref()).collect();
+        assert_eq!(first_cells, vec!["a", "b", "d", "c"]);
+    }
+
+    #[test_case("9", "10", false, Ordering::Less)]
+    #[test_case("-1.5", "-1", false, Ordering::Less)]
+    #[test_case("1,234", "999", false, Ordering::Greater)]
+    #[test_case("$5", "12%", false, Ordering::Less)]
+    #[test_case("1e3", "999", false, Ordering::Greater)]
+    #[test_case("NaN", "10", false, Ordering::Greater)]
+    #[test_case("n/a", "10", false, Ordering::Greater)]
+    #[test_case("", "0", false, Ordering::Greater)]
+    #[test_case("9", "10", true, Ordering::Greater)]
+    #[test_case("n/a", "10", true, Ordering::Greater)]
+    #[test_case("12,34", "100", true, Ordering::Greater)]
+    #[test_case("", "0", true, Ordering::Greater)]
+    fn test_compare_numeric(a: &str, b: &str, descending: bool, correct: Ordering) {
+        assert_eq!(compare_numeric(a, b, descending), correct);
+    }
+}
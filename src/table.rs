@@ -0,0 +1,268 @@
+//! Public, structured representation of a table.
+//!
+//! A `Table` owns the text of every cell, so it can be read or changed without re-parsing Markdown.  Rendering a `Table` with `Table::to_markdown` and `FormatOptions::default()` gives exactly the text `format_tables` would place at `Table::range`.
+
+use comrak::nodes::TableAlignment;
+use std::ops::{Range, RangeInclusive};
+
+use crate::ast::{get_tables, TableInDocument};
+use crate::directives::Directives;
+use crate::options::FormatOptions;
+use crate::sort::{sort_rows, SortOptions};
+use crate::table_formatter::{
+    get_table_content_rows, get_table_layout, infer_alignments, render_table, repair_rows, Cell,
+    Row, TableLayout,
+};
+
+/// Alignment of a table column as set in the delimiter row.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    /// `---`
+    #[default]
+    None,
+    /// `:---`
+    Left,
+    /// `:---:`
+    Center,
+    /// `---:`
+    Right,
+}
+
+impl From<TableAlignment> for Alignment {
+    fn from(value: TableAlignment) -> Self {
+        match value {
+            TableAlignment::None => Alignment::None,
+            TableAlignment::Left => Alignment::Left,
+            TableAlignment::Center => Alignment::Center,
+            TableAlignment::Right => Alignment::Right,
+        }
+    }
+}
+
+impl From<Alignment> for TableAlignment {
+    fn from(value: Alignment) -> Self {
+        match value {
+            Alignment::None => TableAlignment::None,
+            Alignment::Left => TableAlignment::Left,
+            Alignment::Center => TableAlignment::Center,
+            Alignment::Right => TableAlignment::Right,
+        }
+    }
+}
+
+/// A GitHub Flavored Markdown table.
+///
+/// ## Values
+///
+/// - `headers` text of each header cell
+/// - `alignments` alignment of each column defined in the delimiter row
+/// - `rows` text of each cell in each body row
+/// - `range` byte offsets of every line the table is on in its document
+/// - `lines` 1-indexed line numbers of the first and last line of the table in its document
+///
+/// Cell text never has leading or trailing whitespace.  Vertical bars in cell text must be escaped as `\|`, and cell text must not contain a line break, which would end the row.
+///
+/// The header row and the delimiter row of GitHub Flavored Markdown tables have the same number of cells.  If `headers` and `alignments` have different lengths, the shorter one is padded with empty header cells or `Alignment::None` when the table is rendered.
+///
+/// Rows are not required to have the same number of cells as `alignments`.  Extra cells are kept so that no text is ever lost, even though they are not rendered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub alignments: Vec<Alignment>,
+    pub rows: Vec<Vec<String>>,
+    pub range: Range<usize>,
+    pub lines: RangeInclusive<usize>,
+    layout: TableLayout,
+    directives: Directives,
+}
+
+impl Table {
+    /// Create a table that is not part of any document.
+    ///
+    /// `range` is `0..0` and `lines` is `0..=0` since line numbers start at 1.  The table is not indented, and rows are separated by `\n`.
+    pub fn new(headers: Vec<String>, alignments: Vec<Alignment>, rows: Vec<Vec<String>>) -> Self {
+        Table {
+            headers,
+            alignments,
+            rows,
+            range: 0..0,
+            lines: 0..=0,
+            layout: TableLayout::default(),
+            directives: Directives::default(),
+        }
+    }
+
+    /// Render the table as Markdown using the style in `options`.
+    ///
+    /// The result keeps the indentation (such as blockquote markers or list item indentation) and line endings the table had in its document, so it can directly replace `range`.  Directives in an HTML comment before the table in its document are applied too, except that a `sort` directive is dropped once the rows have been explicitly ordered with `sort`, `move_row`, or `insert_row`.
+    pub fn to_markdown(&self, options: &FormatOptions) -> String {
+        let to_row = |cells: &Vec<String>| -> Row {
+            cells.iter().map(|cell| Cell::from(cell.as_str())).collect()
+        };
+
+        let mut content_rows: Vec<Row> = std::iter::once(&self.headers)
+            .chain(&self.rows)
+            .map(to_row)
+            .collect();
+
+        let mut alignments: Vec<TableAlignment> = self
+            .alignments
+            .iter()
+            .map(|alignment| TableAlignment::from(*alignment))
+            .collect();
+
+        // the header row must have exactly one cell for each delimiter cell to be parsed as a table
+        let column_count = alignments.len().max(self.headers.len());
+        content_rows[0].resize(column_count, Cell::from(""));
+        alignments.resize(column_count, TableAlignment::None);
+
+        if options.infer_alignment {
+            infer_alignments(&content_rows, &mut alignments);
+        }
+
+        let (options, _) = self
+            .directives
+            .apply(&mut content_rows, &mut alignments, options);
+
+        if options.repair {
+            repair_rows(&mut content_rows, &mut alignments);
+        }
+
+        render_table(&content_rows, &alignments, &self.layout, &options)
+    }
+
+    /// Number of columns defined by the delimiter row.
+    pub fn column_count(&self) -> usize {
+        self.alignments.len()
+    }
+
+    /// Insert an empty body row at `index`, shifting every row after it down.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `index > self.rows.len()`.
+    pub fn insert_row(&mut self, index: usize) {
+        self.rows
+            .insert(index, vec![String::new(); self.column_count()]);
+        self.directives.sort = None;
+    }
+
+    /// Remove and return the body row at `index`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn delete_row(&mut self, index: usize) -> Vec<String> {
+        self.rows.remove(index)
+    }
+
+    /// Move the body row at `from` so that it ends up at `to`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `from` or `to` is out of bounds.
+    pub fn move_row(&mut self, from: usize, to: usize) {
+        let row = self.rows.remove(from);
+        self.rows.insert(to, row);
+        self.directives.sort = None;
+    }
+
+    /// Insert a column with an empty header, `alignment`, and empty body cells at `index`, shifting every column after it right.
+    ///
+    /// ## Remarks
+    ///
+    /// Body rows with fewer than `index` cells are left unchanged, so that their cells stay in the same columns.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `index > self.column_count()`.
+    pub fn insert_column(&mut self, index: usize, alignment: Alignment) {
+        self.alignments.insert(index, alignment);
+
+        for row in std::iter::once(&mut self.headers).chain(self.rows.iter_mut()) {
+            if index <= row.len() {
+                row.insert(index, String::new());
+            }
+        }
+    }
+
+    /// Remove the column at `index` from the header row, the delimiter row, and every body row that has a cell in it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `index >= self.column_count()`.
+    pub fn delete_column(&mut self, index: usize) {
+        self.alignments.remove(index);
+
+        for row in std::iter::once(&mut self.headers).chain(self.rows.iter_mut()) {
+            if index < row.len() {
+                row.remove(index);
+            }
+        }
+    }
+
+    /// Stable sort of the body rows by the column in `options`.  The header row never moves, and the new order takes precedence over a `sort` directive in the document.
+    ///
+    /// ## Returns
+    ///
+    /// Whether the table has the column in `options`.  If it doesn't, the table is not changed.
+    pub fn sort(&mut self, options: &SortOptions) -> bool {
+        let Some(column) = options.column_index(&self.headers, self.column_count()) else {
+            return false;
+        };
+
+        sort_rows(&mut self.rows, column, options.method, options.descending);
+        self.directives.sort = None;
+
+        true
+    }
+
+    /// Move the column at `from`, including its alignment, so that it ends up at `to`.
+    ///
+    /// ## Remarks
+    ///
+    /// Body rows without a cell in both columns are left unchanged.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `from` or `to` is not less than `self.column_count()`.
+    pub fn move_column(&mut self, from: usize, to: usize) {
+        let alignment = self.alignments.remove(from);
+        self.alignments.insert(to, alignment);
+
+        for row in std::iter::once(&mut self.headers).chain(self.rows.iter_mut()) {
+            if from.max(to) < row.len() {
+                let cell = row.remove(from);
+                row.insert(to, cell);
+            }
+        }
+    }
+}
+
+impl From<&TableInDocument<'_>> for Table {
+    fn from(table: &TableInDocument<'_>) -> Self {
+        let to_strings =
+            |row: Row| -> Vec<String> { row.into_iter().map(|cell| cell.content).collect() };
+
+        let mut content_rows = get_table_content_rows(table).rows.into_iter();
+
+        Table {
+            headers: content_rows.next().map(to_strings).unwrap_or_default(),
+            alignments: table
+                .alignments
+                .iter()
+                .map(|alignment| Alignment::from(*alignment))
+                .collect(),
+            rows: content_rows.map(to_strings).collect(),
+            range: table.range.clone(),
+            lines: table.lines.clone(),
+            layout: get_table_layout(table),
+            directives: table.directives.clone(),
+        }
+    }
+}
+
+/// Return every GitHub Flavored Markdown table in `doc`, ordered by position.
+pub fn extract<T: AsRef<str>>(doc: T) -> Vec<Table> {
+    get_tables(doc.as_ref()).iter().map(Table::from).collect()
+}
@@ -1,15 +1,20 @@
 //! See `table_formatter_behavior.md` for a description of behavior specific to this formatter.
 
 use comrak::nodes::TableAlignment;
+use std::ops::{Range, RangeInclusive};
 use unicode_display_width::width;
 
 use crate::ast::{get_tables, TableInDocument};
+use crate::options::{DelimiterStyle, FormatOptions, HeaderAlignment};
 
-const INDEX_OF_DELIMITER_ROW: usize = 1;
+pub(crate) const INDEX_OF_DELIMITER_ROW: usize = 1;
 
-type Row = Vec<Cell>;
-struct TableRows {
-    rows: Vec<Row>,
+/// Characters of the container indentation before a row, such as list item indentation and blockquote markers.
+pub(crate) const INDENTATION_CHARS: [char; 3] = [' ', '>', '\t'];
+
+pub(crate) type Row = Vec<Cell>;
+pub(crate) struct TableRows {
+    pub(crate) rows: Vec<Row>,
 }
 
 /// Represents a table cell.
@@ -20,15 +25,14 @@ struct TableRows {
 /// - `content` is the text of the cell with no leading or trailing whitespace.
 ///
 #[derive(Clone, Debug, PartialEq, Eq)]
-struct Cell {
-    content: String,
-    visual_length: usize,
+pub(crate) struct Cell {
+    pub(crate) content: String,
+    pub(crate) visual_length: usize,
 }
 
-impl From<Vec<char>> for Cell {
-    fn from(value: Vec<char>) -> Self {
-        let cell_string: String = value.into_iter().collect();
-        let cell_string: String = cell_string.trim().to_string();
+impl From<&str> for Cell {
+    fn from(value: &str) -> Self {
+        let cell_string: String = value.trim().to_string();
 
         Cell {
             visual_length: width(&cell_string) as usize, // no cell should exceed 2 billion columns
@@ -37,9 +41,15 @@ impl From<Vec<char>> for Cell {
     }
 }
 
+impl AsRef<str> for Cell {
+    fn as_ref(&self) -> &str {
+        &self.content
+    }
+}
+
 /// Return the minimum number of characters a delimiter cell can have given a specific alignment, not including any whitespace padding.
 ///
-/// The minimum alignment cell text is given in the following table:
+/// With the default of three hyphens, the minimum alignment cell text is given in the following table:
 ///
 /// | Alignment       | Minimum characters |
 /// | :-------------- | :----------------- |
@@ -48,15 +58,20 @@ impl From<Vec<char>> for Cell {
 /// | center          | `:---:`            |
 /// | not specified   | `---`              |
 ///
+/// ## Parameters
+///
+/// - `alignment`
+/// - `min_dashes` minimum number of hyphens, not including colons
+///
 /// ## Returns
 ///
 /// The number of characters in the delimiter row
 ///
-fn get_alignment_cell_minimum_width(alignment: &TableAlignment) -> u8 {
+fn get_alignment_cell_minimum_width(alignment: &TableAlignment, min_dashes: usize) -> usize {
     match alignment {
-        TableAlignment::Center => 5,
-        TableAlignment::Left | TableAlignment::Right => 4,
-        TableAlignment::None => 3,
+        TableAlignment::Center => min_dashes + 2,
+        TableAlignment::Left | TableAlignment::Right => min_dashes + 1,
+        TableAlignment::None => min_dashes,
     }
 }
 
@@ -66,21 +81,46 @@ fn get_alignment_cell_minimum_width(alignment: &TableAlignment) -> u8 {
 ///
 /// - `content_rows` all rows of the table except for the delimiter row
 /// - `alignments`
+/// - `options`
 ///
 /// ## Remarks
 ///
-/// If there are cells with a column number greater than the number of alignment cells in the delimiter row, they are ignored by this function.
+/// If there are cells with a column number greater than the number of alignment cells in the delimiter row, they are ignored by this function unless `options.align_extra_columns` is set.  Those columns have no delimiter cell, so they have no minimum width.
+///
+/// The second row (delimiter row) has special consideration.  The number of hyphens of each cell as it exists in the pre-formatted table is ignored.  Instead a minimum size is given depending on the orientation, so that it has at least `options.min_delimiter_dashes` hyphens.  This enables the table to shrink as appropriate.  As such, only the *content* rows should be passed to this function.
 ///
-/// The second row (delimiter row) has special consideration.  The number of hyphens of each cell as it exists in the pre-formatted table is ignored.  Instead a minimum size is given depending on the orientation, so that it has at least 3 hyphens.  This enables the table to shrink as appropriate.  As such, only the *content* rows should be passed to this function.
+/// A column is also never narrower than its entry in `options.min_column_widths`, if it has one.
 ///
 /// The result of this function is used to set the amount of whitespace padding to ensure each entry has the same width in each column.
 ///
 /// The maximum width does not include any leading or trailing whitespace.
 ///
-fn get_col_max_widths(content_rows: &[Row], alignments: &[TableAlignment]) -> Vec<usize> {
+fn get_col_max_widths(
+    content_rows: &[Row],
+    alignments: &[TableAlignment],
+    options: &FormatOptions,
+) -> Vec<usize> {
     let mut max_widths: Vec<usize> = Vec::new();
 
-    for (index, _) in alignments.iter().enumerate() {
+    let column_count = match options.align_extra_columns {
+        true => content_rows
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0)
+            .max(alignments.len()),
+        false => alignments.len(),
+    };
+
+    for index in 0..column_count {
+        let delimiter_width = match (alignments.get(index), options.delimiter_style) {
+            // the delimiter cell doesn't need to fit in the column
+            (_, DelimiterStyle::Minimal) | (None, _) => 0,
+            (Some(alignment), _) => {
+                get_alignment_cell_minimum_width(alignment, options.min_delimiter_dashes)
+            }
+        };
+
         max_widths.push(
             content_rows
                 .iter()
@@ -88,26 +128,49 @@ fn get_col_max_widths(content_rows: &[Row], alignments: &[TableAlignment]) -> Ve
                 .map(|row| row[index].visual_length)
                 .max()
                 .unwrap_or(0)
-                .max(get_alignment_cell_minimum_width(&alignments[index]) as usize),
+                .max(delimiter_width)
+                .max(options.min_column_widths.get(index).copied().unwrap_or(0)),
         );
     }
 
     max_widths
 }
 
+/// Return the colons and hyphens of a delimiter cell that is `width` characters wide.
+fn format_delimiter(alignment: &TableAlignment, width: usize) -> String {
+    match alignment {
+        TableAlignment::Center => format!(":{}:", String::from("-").repeat(width - 2)),
+        TableAlignment::Left => format!(":{}", String::from("-").repeat(width - 1)),
+        TableAlignment::Right => format!("{}:", String::from("-").repeat(width - 1)),
+        TableAlignment::None => String::from("-").repeat(width),
+    }
+}
+
 /// Get the formatted string to place in an delimiter cell.
 ///
 /// ## Parameters
 ///
 /// - `alignment`
 /// - `width` visual width of the cell not including leading or trailing whitespace
-///
-fn format_delimiter_cell(alignment: &TableAlignment, width: usize) -> String {
-    match alignment {
-        TableAlignment::Center => format!(" :{}: ", String::from("-").repeat(width - 2)),
-        TableAlignment::Left => format!(" :{} ", String::from("-").repeat(width - 1)),
-        TableAlignment::Right => format!(" {}: ", String::from("-").repeat(width - 1)),
-        TableAlignment::None => format!(" {} ", String::from("-").repeat(width)),
+/// - `options` the padding and delimiter style of the cell
+///
+fn format_delimiter_cell(
+    alignment: &TableAlignment,
+    width: usize,
+    options: &FormatOptions,
+) -> String {
+    let padding = options.cell_padding;
+
+    match options.delimiter_style {
+        DelimiterStyle::Padded => {
+            let padding = String::from(" ").repeat(padding);
+            format!("{padding}{}{padding}", format_delimiter(alignment, width))
+        }
+        DelimiterStyle::Minimal => format_delimiter(
+            alignment,
+            get_alignment_cell_minimum_width(alignment, options.min_delimiter_dashes),
+        ),
+        DelimiterStyle::Filled => format_delimiter(alignment, width + 2 * padding),
     }
 }
 
@@ -115,6 +178,7 @@ fn format_delimiter_cell(alignment: &TableAlignment, width: usize) -> String {
 fn get_normalized_delimiter_row(
     column_alignment: &[TableAlignment],
     column_max_widths: &[usize],
+    options: &FormatOptions,
 ) -> Vec<String> {
     if column_alignment.len() != column_max_widths.len() {
         panic!(
@@ -123,7 +187,7 @@ fn get_normalized_delimiter_row(
     }
 
     std::iter::zip(column_alignment.iter(), column_max_widths.iter())
-        .map(|(alignment, width)| format_delimiter_cell(alignment, *width))
+        .map(|(alignment, width)| format_delimiter_cell(alignment, *width, options))
         .collect()
 }
 
@@ -134,22 +198,25 @@ fn get_normalized_delimiter_row(
 /// - `cell`
 /// - `length` max visual length of any element (not including any leading or trailing whitespace)
 /// - `align`
+/// - `padding` number of spaces on each side of the cell
 ///
 /// ## Returns
 ///
 /// String that
 ///
 /// - is aligned according to `align`,
-/// - has at least one space on each end, and
-/// - has a visual length of 2 greater than `length`.
+/// - has at least `padding` spaces on each end, and
+/// - has a visual length of `2 * padding` greater than `length`.
 ///
-fn align_cell(cell: &Cell, align: &TableAlignment, length: &usize) -> String {
+fn align_cell(cell: &Cell, align: &TableAlignment, length: &usize, padding: usize) -> String {
     if cell.visual_length > *length {
         panic!(
             "Invalid `length` argument.  It must be greater than or equal to `cell.visual_length`"
         )
     }
 
+    let padding = String::from(" ").repeat(padding);
+
     match align {
         TableAlignment::Center if *length > cell.visual_length => {
             // Integer division truncates *toward zero* to the nearest integer.
@@ -160,20 +227,20 @@ fn align_cell(cell: &Cell, align: &TableAlignment, length: &usize) -> String {
             let trailing_whitespace = String::from(" ").repeat(trailing_whitespace);
 
             format!(
-                " {0}{1}{2} ",
+                "{padding}{0}{1}{2}{padding}",
                 leading_whitespace, cell.content, trailing_whitespace
             )
         }
         TableAlignment::Right => {
             format!(
-                " {0}{1} ",
+                "{padding}{0}{1}{padding}",
                 String::from(" ").repeat(length - cell.visual_length),
                 cell.content
             )
         }
         _ => {
             format!(
-                " {0}{1} ",
+                "{padding}{0}{1}{padding}",
                 cell.content,
                 String::from(" ").repeat(length - cell.visual_length)
             )
@@ -181,69 +248,351 @@ fn align_cell(cell: &Cell, align: &TableAlignment, length: &usize) -> String {
     }
 }
 
-/// Return the text preceding the start of the table on its first line.
+/// Return the blockquote markers and whitespace at the start of `line`.
+pub(crate) fn get_line_indentation(line: &str) -> &str {
+    let end = line
+        .find(|character| !INDENTATION_CHARS.contains(&character))
+        .unwrap_or(line.len());
+
+    &line[..end]
+}
+
+/// Return the text to place before the first row of the table and before every following row.
 ///
 /// ## Remarks
 ///
 /// Tables can be nested inside one or more blockquotes so long as the blockquote indent is consistent for the entire table.
 ///
+/// Tables can also be nested inside list items and footnote definitions.  If the table starts on the same line as the list item marker or footnote label, the marker is kept on the first row and every following row keeps the continuation indentation of the delimiter row.  The same goes for a header row that is a lazy continuation line, which has no indentation at all.
+///
 /// ## Returns
 ///
-/// If the table starts at the beginning of the line, return `None`.  Otherwise, return a String composed of spaces and `>`.
+/// If the table starts at the beginning of the line, return `None`.  Otherwise, return the indentation of the first row and the indentation of every following row.
 ///
-fn get_table_indentation(table_header: &str) -> Option<String> {
-    let mut indentation = String::new();
-
-    let allowed_chars = [' ', '>'];
+fn get_table_indentation(table: &TableInDocument<'_>) -> Option<(String, String)> {
+    let prefix = get_first_line_prefix(table);
 
-    for character in table_header.chars() {
-        if allowed_chars.contains(&character) {
-            indentation.push(character);
-        } else {
-            break;
-        }
+    if prefix.is_empty() && !table.lazy_header {
+        return None;
     }
 
-    if indentation.is_empty() {
-        None
-    } else {
-        Some(indentation)
+    if get_line_indentation(prefix) == prefix && !table.lazy_header {
+        return Some((prefix.to_string(), prefix.to_string()));
     }
+
+    let delimiter_row = table.text.lines().nth(INDEX_OF_DELIMITER_ROW);
+    let continuation = get_line_indentation(delimiter_row.unwrap_or_default());
+
+    Some((prefix.to_string(), continuation.to_string()))
+}
+
+/// Return the text preceding the start of the table on its first line.
+fn get_first_line_prefix<'a>(table: &TableInDocument<'a>) -> &'a str {
+    let first_line = table.text.lines().next().unwrap_or_default();
+
+    first_line
+        .get(..table.first_line_offset)
+        .unwrap_or_default()
 }
 
 /// Return owned `TableRow` structs with owned data inside that represent the contents of each row.
 ///
 /// The delimiter row is ignored by this function as that is parsed by `comrak` and can be regenerated based on the alignment values.
-fn get_table_content_rows(table: &str) -> TableRows {
-    let content_row_iter = table.lines().take(1).chain(table.lines().skip(2));
+pub(crate) fn get_table_content_rows(table: &TableInDocument<'_>) -> TableRows {
+    let header = table
+        .text
+        .lines()
+        .next()
+        .map(|line| line.get(table.first_line_offset..).unwrap_or(line));
+
+    let content_row_iter = header.into_iter().chain(table.text.lines().skip(2));
     let rows = content_row_iter.map(parse_row_text).collect();
 
     TableRows { rows }
 }
 
-/// Format a GFM table.
+/// The text around the rows of a table that formatting must keep.
+///
+/// - `indentation` the text before the first row and the text before every following row.  See `get_table_indentation`.
+/// - `line_ending` the text between rows
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TableLayout {
+    pub(crate) indentation: Option<(String, String)>,
+    pub(crate) line_ending: &'static str,
+}
+
+impl Default for TableLayout {
+    fn default() -> Self {
+        TableLayout {
+            indentation: None,
+            line_ending: "\n",
+        }
+    }
+}
+
+/// Return the layout of `table` in its document.
+pub(crate) fn get_table_layout(table: &TableInDocument<'_>) -> TableLayout {
+    const CRLF: &str = "\r\n";
+
+    TableLayout {
+        indentation: get_table_indentation(table),
+        line_ending: if table.text.contains(CRLF) {
+            CRLF
+        } else {
+            "\n"
+        },
+    }
+}
+
+/// Render the content rows of a GFM table.
 ///
-/// - each cell has at least one space at the start and end.
-/// - each cell has the same visual width as each of the other cells in its column if that column has an alignment cell
+/// - each cell has at least one space at the start and end, unless `options.compact` is set
+/// - each cell has the same visual width as each of the other cells in its column if that column has an alignment cell, unless `options.compact` is set
 /// - no text is ever deleted except for leading and trailing whitespace in a cell
 /// - will not panic on malformed tables (any panic is a bug)
-fn format_table(table: &TableInDocument<'_>) -> String {
-    let table_rows = get_table_content_rows(table.text);
+///
+/// ## Parameters
+///
+/// - `content_rows` all rows of the table except for the delimiter row
+/// - `alignments`
+/// - `layout`
+/// - `options`
+///
+pub(crate) fn render_table(
+    content_rows: &[Row],
+    alignments: &[TableAlignment],
+    layout: &TableLayout,
+    options: &FormatOptions,
+) -> String {
+    let mut rows = match options.compact {
+        true => render_compact_rows(content_rows, alignments),
+        false => render_aligned_rows(content_rows, alignments, options),
+    };
+
+    if !options.outer_pipes && can_omit_outer_pipes(content_rows, alignments) {
+        let padding = match options.compact {
+            true => 0,
+            false => options.cell_padding,
+        };
+        let delimiter_padding = match (options.compact, options.delimiter_style) {
+            (false, DelimiterStyle::Padded) => " ".repeat(padding),
+            (false, DelimiterStyle::Filled) => "-".repeat(padding),
+            _ => String::new(),
+        };
+        let padding = " ".repeat(padding);
+
+        let rows_without_pipes: Vec<String> = rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| match index == INDEX_OF_DELIMITER_ROW {
+                true => omit_outer_pipes(row, &delimiter_padding),
+                false => omit_outer_pipes(row, &padding),
+            })
+            .collect();
+
+        // see `can_omit_outer_pipes`
+        if !rows_without_pipes
+            .iter()
+            .any(|row| row.starts_with([' ', '\t']))
+        {
+            rows = rows_without_pipes;
+        }
+    }
+
+    if let Some((first, continuation)) = &layout.indentation {
+        rows = rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, row)| match index {
+                0 => format!("{first}{row}"),
+                _ => format!("{continuation}{row}"),
+            })
+            .collect();
+    }
+
+    rows.join(layout.line_ending)
+}
+
+/// Return whether a row starting with `cell` could be parsed as the start of another block instead of a table row, such as a list item, heading, or blockquote.
+fn starts_block(cell: &str) -> bool {
+    if cell.starts_with(['-', '+', '*', '_', '#', '>', '=', '`', '~', '<']) {
+        return true;
+    }
+
+    // ordered list item, which needs whitespace after the marker, unlike a number such as `1.5`
+    let digits = cell.len() - cell.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    digits > 0
+        && cell[digits..]
+            .strip_prefix(['.', ')'])
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t']))
+}
+
+/// Return whether every row of a table can be written without leading and trailing vertical bars and still be parsed as the same table.
+///
+/// ## Remarks
+///
+/// Without a leading or trailing vertical bar,
+///
+/// - a single column table has a delimiter row that is parsed as a thematic break or setext heading underline
+/// - an empty first or last cell is indistinguishable from no cell at all
+/// - a first cell such as `- item` starts a new block and ends the table
+///
+/// A row that starts with whitespace once its vertical bar is removed, such as a row of a right aligned first column, would be read as indented the next time the table is formatted, or even as an indented code block.  `render_table` checks for those rows after rendering.
+fn can_omit_outer_pipes(content_rows: &[Row], alignments: &[TableAlignment]) -> bool {
+    alignments.len() > 1
+        && content_rows
+            .iter()
+            .all(|row| match (row.first(), row.last()) {
+                (Some(first), Some(last)) if row.len() > 1 => {
+                    !first.content.is_empty()
+                        && !last.content.is_empty()
+                        && !starts_block(&first.content)
+                }
+                _ => false,
+            })
+}
+
+/// Remove the leading and trailing vertical bar of a rendered `row`, along with the `padding` after the leading vertical bar.
+///
+/// Every row starts with padding of the same width, so every row shifts by the same amount and columns stay aligned.  The padding of a delimiter row in the filled style is hyphens after any alignment colon, so a leading colon is kept.
+fn omit_outer_pipes(row: &str, padding: &str) -> String {
+    let row = row.strip_prefix('|').unwrap_or(row);
+    let row = row.strip_suffix('|').unwrap_or(row);
+
+    let (colon, rest) = row.split_at(usize::from(row.starts_with(':')));
+    let rest = rest.strip_prefix(padding).unwrap_or(rest);
+
+    format!("{colon}{rest}").trim_end().to_string()
+}
+
+/// Return the delimiter cell of the compact style, which has no padding and a single hyphen.
+fn format_compact_delimiter_cell(alignment: &TableAlignment) -> &'static str {
+    match alignment {
+        TableAlignment::Center => ":-:",
+        TableAlignment::Left => ":-",
+        TableAlignment::Right => "-:",
+        TableAlignment::None => "-",
+    }
+}
+
+/// Render every row, including the delimiter row, in the compact style.
+///
+/// Cells have no padding and columns are not aligned, which keeps diffs small at the cost of readability in the source.
+fn render_compact_rows(content_rows: &[Row], alignments: &[TableAlignment]) -> Vec<String> {
+    let delimiter_row: Vec<&str> = alignments
+        .iter()
+        .map(format_compact_delimiter_cell)
+        .collect();
+    let delimiter_row = format!("|{}|", delimiter_row.join("|"));
+
+    let mut rows: Vec<String> = content_rows
+        .iter()
+        .map(|row| {
+            let cells: Vec<&str> = row.iter().map(|cell| cell.content.as_str()).collect();
+            format!("|{}|", cells.join("|"))
+        })
+        .collect();
+
+    rows.insert(INDEX_OF_DELIMITER_ROW.min(rows.len()), delimiter_row);
+
+    rows
+}
+
+/// Return the display widths of the text before and after the decimal separator of `content`.  Text without a `.` is all before the separator, like an integer.
+fn get_decimal_widths(content: &str) -> (usize, usize) {
+    let (integer, fraction) = content.split_at(content.find('.').unwrap_or(content.len()));
+
+    (width(integer) as usize, width(fraction) as usize)
+}
+
+/// Pad the body cells of every column in `options.decimal_columns` so that their decimal separators line up, and return the alignment to render each column with.
+///
+/// Padded cells all have the same width, so they are aligned as a block by the alignment of their column.  A decimal column keeps right alignment, but left and center alignments become no alignment, since those would move the separators out of line.
+fn align_decimal_columns(
+    content_rows: &[Row],
+    alignments: &[TableAlignment],
+    options: &FormatOptions,
+) -> (Vec<Row>, Vec<TableAlignment>) {
+    let mut rows = content_rows.to_vec();
+    let mut alignments = alignments.to_vec();
+
+    for &column in &options.decimal_columns {
+        let Some(alignment) = alignments.get_mut(column) else {
+            continue;
+        };
+        if *alignment != TableAlignment::Right {
+            *alignment = TableAlignment::None;
+        }
+
+        let body_cells = || {
+            rows.iter()
+                .skip(1)
+                .filter_map(|row| row.get(column))
+                .filter(|cell| !cell.content.is_empty())
+        };
+
+        let integer_width = body_cells()
+            .map(|cell| get_decimal_widths(&cell.content).0)
+            .max()
+            .unwrap_or(0);
+        let fraction_width = body_cells()
+            .map(|cell| get_decimal_widths(&cell.content).1)
+            .max()
+            .unwrap_or(0);
+
+        for cell in rows
+            .iter_mut()
+            .skip(1)
+            .filter_map(|row| row.get_mut(column))
+            .filter(|cell| !cell.content.is_empty())
+        {
+            let (integer, fraction) = get_decimal_widths(&cell.content);
+
+            cell.content = format!(
+                "{}{}{}",
+                " ".repeat(integer_width - integer),
+                cell.content,
+                " ".repeat(fraction_width - fraction)
+            );
+            cell.visual_length = integer_width + fraction_width;
+        }
+    }
+
+    (rows, alignments)
+}
+
+/// Render every row, including the delimiter row, with padded cells so that each column is aligned.
+fn render_aligned_rows(
+    content_rows: &[Row],
+    alignments: &[TableAlignment],
+    options: &FormatOptions,
+) -> Vec<String> {
+    let (content_rows, alignments) = align_decimal_columns(content_rows, alignments, options);
+    let (content_rows, alignments) = (content_rows.as_slice(), alignments.as_slice());
 
     // Column "content" width (the length of the longest cell in each column), **without padding**
-    let column_max_widths: Vec<usize> = get_col_max_widths(&table_rows.rows, &table.alignments);
+    let column_max_widths: Vec<usize> = get_col_max_widths(content_rows, alignments, options);
 
-    let delimiter_row = get_normalized_delimiter_row(&table.alignments, &column_max_widths);
+    let delimiter_row =
+        get_normalized_delimiter_row(alignments, &column_max_widths[..alignments.len()], options);
     let delimiter_row: String = format!("|{}|", delimiter_row.join("|"));
 
+    // only changes whitespace, so the delimiter row keeps the alignments of the columns
+    let header_alignment: Option<TableAlignment> = match options.header_alignment {
+        HeaderAlignment::Column => None,
+        HeaderAlignment::Left => Some(TableAlignment::Left),
+        HeaderAlignment::Center => Some(TableAlignment::Center),
+        HeaderAlignment::Right => Some(TableAlignment::Right),
+    };
+
     // align all the cells
     let mut rows: Vec<String> = Vec::new();
-    for row in table_rows.rows {
+    for (row_index, row) in content_rows.iter().enumerate() {
         let mut aligned_cells: Vec<String> = Vec::new();
         for (index, cell) in row.iter().enumerate() {
-            let align: &TableAlignment = match &table.alignments.get(index) {
-                Some(val) => val,
-                None => &TableAlignment::None,
+            let align: &TableAlignment = match (row_index, &header_alignment) {
+                (0, Some(val)) => val,
+                _ => alignments.get(index).unwrap_or(&TableAlignment::None),
             };
 
             let length: usize = match column_max_widths.get(index) {
@@ -251,85 +600,262 @@ fn format_table(table: &TableInDocument<'_>) -> String {
                 None => width(&cell.content) as usize,
             };
 
-            aligned_cells.push(align_cell(cell, align, &length));
+            aligned_cells.push(align_cell(cell, align, &length, options.cell_padding));
         }
 
         let row: String = format!("|{}|", aligned_cells.join("|"));
         rows.push(row);
     }
 
-    rows.insert(INDEX_OF_DELIMITER_ROW, delimiter_row);
+    rows.insert(INDEX_OF_DELIMITER_ROW.min(rows.len()), delimiter_row);
 
-    if let Some(indentation) = get_table_indentation(table.text.lines().next().unwrap()) {
-        rows = rows
-            .into_iter()
-            .map(|row| format!("{indentation}{row}"))
-            .collect();
+    rows
+}
+
+/// Append empty cells to every row with fewer cells than the widest row, and a delimiter cell without colons for every column past the last column of the delimiter row.
+///
+/// ## Parameters
+///
+/// - `content_rows` all rows of the table except for the delimiter row, including the header row, which must have as many cells as the delimiter row
+/// - `alignments`
+///
+pub(crate) fn repair_rows(content_rows: &mut [Row], alignments: &mut Vec<TableAlignment>) {
+    let column_count = content_rows
+        .iter()
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0)
+        .max(alignments.len());
+
+    alignments.resize(column_count, TableAlignment::None);
+
+    for row in content_rows {
+        row.resize(column_count, Cell::from(""));
     }
+}
 
-    const CRLF: &str = "\r\n";
-    if table.text.contains(CRLF) {
-        return rows.join(CRLF);
+/// Currency symbols allowed before or after a number when inferring alignment.
+const CURRENCY_SYMBOLS: [char; 6] = ['$', '€', '£', '¥', '₹', '¢'];
+
+/// Return whether `content` is a number, a percentage, or an amount of currency, such as `-1,234.5`, `12%`, or `$3.50`.
+fn is_numeric(content: &str) -> bool {
+    parse_numeric(content).is_some()
+}
+
+/// Return the value of a number, a percentage, or an amount of currency, such as `-1,234.5`, `12%`, or `$3.50`, ignoring the percent sign, currency symbol, and thousands separators.
+///
+/// Exponents, `NaN`, and `inf` are not numbers.
+pub(crate) fn parse_numeric(content: &str) -> Option<f64> {
+    let negative = content.starts_with('-');
+    let text = content.strip_prefix(['+', '-']).unwrap_or(content);
+    let text = text.strip_prefix(CURRENCY_SYMBOLS).unwrap_or(text);
+    let text = text
+        .strip_suffix('%')
+        .or_else(|| text.strip_suffix(CURRENCY_SYMBOLS))
+        .unwrap_or(text)
+        .trim();
+
+    // a sign can also follow the currency symbol, as in `$-5`
+    let negative = negative ^ text.starts_with('-');
+    let text = text.strip_prefix(['+', '-']).unwrap_or(text);
+
+    let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+
+    // thousands separators split the integer part into groups of three digits, after a first group of one to three digits
+    let mut groups = integer.split(',');
+    let first_group = groups.next().unwrap_or_default();
+    let is_grouped = match integer.contains(',') {
+        true => (1..=3).contains(&first_group.len()) && groups.all(|group| group.len() == 3),
+        false => true,
+    };
+
+    let is_numeric = is_grouped
+        && integer.chars().all(|c| c.is_ascii_digit() || c == ',')
+        && fraction.chars().all(|c| c.is_ascii_digit())
+        && text.chars().any(|c| c.is_ascii_digit());
+
+    if !is_numeric {
+        return None;
     }
 
-    rows.join("\n")
+    let value: f64 = text.replace(',', "").parse().ok()?;
+
+    Some(if negative { -value } else { value })
 }
 
-/// The GFM specification does not distinguish between vertical bars in code blocks or regular vertical bars.  Even in code blocks, they need to be escaped.  Given this, the formatting behavior of this project may look strange.
-fn parse_row_text(line: &str) -> Vec<Cell> {
-    // remove any leading whitespace and all blockquote nesting
-    let line = line.trim_matches(|c| [' ', '>'].contains(&c));
+/// Right align every column without an alignment whose non-empty body cells are all numeric.  See `is_numeric`.
+///
+/// ## Parameters
+///
+/// - `content_rows` all rows of the table except for the delimiter row, as returned by `get_table_content_rows`
+/// - `alignments` alignments parsed from the delimiter row, which are changed in place
+///
+pub(crate) fn infer_alignments(content_rows: &[Row], alignments: &mut [TableAlignment]) {
+    for (index, alignment) in alignments.iter_mut().enumerate() {
+        if *alignment != TableAlignment::None {
+            continue;
+        }
 
-    let mut previous_char_was_backslash = false;
-    let mut char_iter = line.chars();
+        let mut cells = content_rows
+            .iter()
+            .skip(1)
+            .filter_map(|row| row.get(index))
+            .filter(|cell| !cell.content.is_empty())
+            .peekable();
+
+        if cells.peek().is_some() && cells.all(|cell| is_numeric(&cell.content)) {
+            *alignment = TableAlignment::Right;
+        }
+    }
+}
+
+/// Format a GFM table.  See `render_table` for the guarantees of the formatted text.
+///
+/// Alignments are inferred first if `options.infer_alignment` is set.  Then any directives before the table are applied, and the table is repaired if `options.repair` is set.
+pub(crate) fn format_table(table: &TableInDocument<'_>, options: &FormatOptions) -> String {
+    format_table_with_row_order(table, options).0
+}
+
+/// Format a GFM table like `format_table`, also returning the index each body row had in `table`, in the order of the formatted body rows.
+///
+/// The order only differs from the order in `table` if a `sort` directive applies to the table.
+pub(crate) fn format_table_with_row_order(
+    table: &TableInDocument<'_>,
+    options: &FormatOptions,
+) -> (String, Vec<usize>) {
+    let mut table_rows = get_table_content_rows(table);
+    let mut alignments = table.alignments.clone();
+
+    if options.infer_alignment {
+        infer_alignments(&table_rows.rows, &mut alignments);
+    }
+
+    let (options, order) = table
+        .directives
+        .apply(&mut table_rows.rows, &mut alignments, options);
+
+    if options.repair {
+        repair_rows(&mut table_rows.rows, &mut alignments);
+    }
 
-    if line.is_empty() || line == "|" {
+    let formatted = render_table(
+        &table_rows.rows,
+        &alignments,
+        &get_table_layout(table),
+        &options,
+    );
+
+    (formatted, order)
+}
+
+/// Return the byte range of each cell in `line`, not including the vertical bars that separate cells.
+///
+/// Each range includes any whitespace surrounding the cell content.
+///
+/// The GFM specification does not distinguish between vertical bars in code blocks or regular vertical bars.  Even in code blocks, they need to be escaped.  Given this, the formatting behavior of this project may look strange.
+pub(crate) fn get_cell_ranges(line: &str) -> Vec<Range<usize>> {
+    // remove any container indentation, including tabs, and all blockquote nesting
+    let trimmed = line.trim_matches(INDENTATION_CHARS);
+    let trimmed_start = line.len() - line.trim_start_matches(INDENTATION_CHARS).len();
+
+    if trimmed.is_empty() || trimmed == "|" {
         return vec![];
     }
 
+    let mut previous_char_was_backslash = false;
+    let mut char_iter = trimmed.char_indices();
+
     // normalize
-    if line.starts_with('|') {
+    if trimmed.starts_with('|') {
         // ignore the first value
         char_iter.next();
     }
 
-    let mut cells: Vec<Cell> = Vec::new();
-    let mut cell: Vec<char> = Vec::new();
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut cell_start = trimmed_start + char_iter.offset();
 
-    for scalar_value in char_iter {
+    for (index, scalar_value) in char_iter {
         if scalar_value == '|' && !previous_char_was_backslash {
-            cells.push(cell.into());
-            cell = Vec::new();
+            ranges.push(cell_start..trimmed_start + index);
+            cell_start = trimmed_start + index + 1;
+        } else if scalar_value == '\\' {
+            // allow multiples of two backslashes to cancel each other out
+            previous_char_was_backslash = !previous_char_was_backslash;
         } else {
-            if scalar_value == '\\' {
-                // allow multiples of two backslashes to cancel each other out
-                previous_char_was_backslash = !previous_char_was_backslash;
-            } else {
-                previous_char_was_backslash = false;
-            }
-            cell.push(scalar_value);
+            previous_char_was_backslash = false;
         }
     }
 
-    if !line.ends_with('|') {
-        cells.push(cell.into());
+    if !trimmed.ends_with('|') {
+        ranges.push(cell_start..trimmed_start + trimmed.len());
+    }
+
+    ranges
+}
+
+/// Parse the cells of a single table row.
+///
+/// See `get_cell_ranges` for how cells are separated.
+pub(crate) fn parse_row_text(line: &str) -> Vec<Cell> {
+    get_cell_ranges(line)
+        .into_iter()
+        .map(|range| Cell::from(&line[range]))
+        .collect()
+}
+
+/// The tables of a document to format.
+pub(crate) enum Selection {
+    All,
+    /// tables overlapping a byte range
+    Bytes(Range<usize>),
+    /// tables overlapping an inclusive range of 1-indexed lines
+    Lines(RangeInclusive<usize>),
+}
+
+impl Selection {
+    /// Return whether `table` is selected.
+    ///
+    /// An empty byte range selects the table it is in, which is how a cursor position is usually sent.
+    pub(crate) fn contains(&self, table: &TableInDocument<'_>) -> bool {
+        match self {
+            Selection::All => true,
+            Selection::Bytes(range) if range.is_empty() => {
+                table.range.start <= range.start && range.start <= table.range.end
+            }
+            Selection::Bytes(range) => {
+                range.start < table.range.end && table.range.start < range.end
+            }
+            Selection::Lines(lines) => {
+                lines.start() <= table.lines.end() && table.lines.start() <= lines.end()
+            }
+        }
     }
+}
 
-    cells
+pub fn format<T: AsRef<str>>(doc: T, options: &FormatOptions) -> String {
+    format_selection(doc, &Selection::All, options)
 }
 
-pub fn format<T: AsRef<str>>(doc: T) -> String {
+/// Format only the tables in `selection`.  Every other byte of `doc` is left untouched.
+pub(crate) fn format_selection<T: AsRef<str>>(
+    doc: T,
+    selection: &Selection,
+    options: &FormatOptions,
+) -> String {
     let doc: &str = doc.as_ref();
 
     let mut fixed = String::with_capacity((doc.len() as f64 * 1.2) as usize);
 
     let mut last_match = 0;
-    for table in &get_tables(doc) {
+    for table in get_tables(doc)
+        .iter()
+        .filter(|table| selection.contains(table))
+    {
         let start = table.range.start;
         let end = table.range.end;
 
         fixed.push_str(&doc[last_match..start]);
-        fixed.push_str(&format_table(table));
+        fixed.push_str(&format_table(table, options));
         last_match = end;
     }
 
@@ -341,6 +867,18 @@ pub fn format<T: AsRef<str>>(doc: T) -> String {
     fixed
 }
 
+/// Return the 1-indexed, inclusive line ranges of every table in `doc` that would be changed by `format`.
+pub fn get_unformatted_tables<T: AsRef<str>>(
+    doc: T,
+    options: &FormatOptions,
+) -> Vec<RangeInclusive<usize>> {
+    get_tables(doc.as_ref())
+        .iter()
+        .filter(|table| format_table(table, options) != table.text)
+        .map(|table| table.lines.clone())
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -407,4 +945,45 @@ mod test {
         let cells = parse_row_text(input);
         assert_eq!(cells, correct_row);
     }
+
+    #[test_case("12", true; "Integer")]
+    #[test_case("-1,234.5", true; "Negative with thousands separator")]
+    #[test_case("12.5%", true; "Percentage")]
+    #[test_case("$3.50", true; "Dollars")]
+    #[test_case("-$5", true; "Negative currency")]
+    #[test_case("$-5", true; "Sign after currency")]
+    #[test_case("5 €", true; "Currency after number")]
+    #[test_case(".5", true; "No integer part")]
+    #[test_case("1.2.3", false; "Version number")]
+    #[test_case("1e5", false; "Exponent")]
+    #[test_case("NaN", false; "Not a number")]
+    #[test_case("$", false; "Currency without number")]
+    #[test_case(",5", false; "Leading comma")]
+    #[test_case("1,2,3", false; "Commas between single digits")]
+    #[test_case("12,34", false; "Decimal comma")]
+    #[test_case("1234,567", false; "First group too long")]
+    #[test_case("1,234,567", true; "Thousands and millions")]
+    #[test_case("12 ms", false; "Unit")]
+    fn test_is_numeric(content: &str, correct: bool) {
+        assert_eq!(is_numeric(content), correct);
+    }
+
+    #[test_case("-1,234.5", Some(-1234.5); "Negative with thousands separator")]
+    #[test_case("12.5%", Some(12.5); "Percentage")]
+    #[test_case("$-5", Some(-5.0); "Sign after currency")]
+    #[test_case(".5", Some(0.5); "No integer part")]
+    #[test_case("inf", None; "Infinity")]
+    #[test_case("1e3", None; "Exponent")]
+    fn test_parse_numeric(content: &str, correct: Option<f64>) {
+        assert_eq!(parse_numeric(content), correct);
+    }
+
+    #[test_case("| a | b |", &[1..4, 5..8]; "Simple row")]
+    #[test_case("\t| a | b |", &[2..5, 6..9]; "Tab indentation")]
+    #[test_case("a | b", &[0..2, 3..5]; "No leading or trailing |")]
+    #[test_case("> > | a | \\| |", &[5..8, 9..13]; "Blockquote and escaped vertical bar")]
+    #[test_case("|", &[]; "Single vertical bar")]
+    fn test_cell_ranges(input: &str, output: &[Range<usize>]) {
+        assert_eq!(get_cell_ranges(input), output);
+    }
 }
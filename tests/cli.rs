@@ -0,0 +1,247 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+const MDTABLEFMT: &str = env!("CARGO_BIN_EXE_mdtablefmt");
+
+const BASIC_TABLE: &str = include_str!("text_snippets/basic_table.txt");
+const BASIC_TABLE_FIXED: &str = include_str!("text_snippets/basic_table_fixed.txt");
+
+/// Write `contents` to a file in the integration test temporary directory.
+fn write_temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+fn run_with_stdin(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(MDTABLEFMT)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn formats_stdin_to_stdout() {
+    let output = run_with_stdin(&[], BASIC_TABLE);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), BASIC_TABLE_FIXED);
+}
+
+#[test]
+fn formats_files_in_place() {
+    let first = write_temp_file("in_place_first.md", BASIC_TABLE);
+    let second = write_temp_file("in_place_second.md", BASIC_TABLE_FIXED);
+
+    let output = Command::new(MDTABLEFMT)
+        .args([&first, &second])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(fs::read_to_string(first).unwrap(), BASIC_TABLE_FIXED);
+    assert_eq!(fs::read_to_string(second).unwrap(), BASIC_TABLE_FIXED);
+}
+
+#[test]
+fn stdout_flag_leaves_file_unchanged() {
+    let path = write_temp_file("stdout_flag.md", BASIC_TABLE);
+
+    let output = Command::new(MDTABLEFMT)
+        .arg("--stdout")
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), BASIC_TABLE_FIXED);
+    assert_eq!(fs::read_to_string(path).unwrap(), BASIC_TABLE);
+}
+
+#[test]
+fn keeps_line_endings() {
+    let input = "# CRLF\r\n\r\n|A|B|\r\n|-|-|\r\n|C|D|\r\n\r\n|A|B|\n|-|-|\n";
+    let correct = "# CRLF\r\n\r\n| A   | B   |\r\n| --- | --- |\r\n| C   | D   |\r\n\r\n| A   | B   |\n| --- | --- |\n";
+    let path = write_temp_file("line_endings.md", input);
+
+    let output = Command::new(MDTABLEFMT).arg(&path).output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(path).unwrap(), correct);
+}
+
+#[test]
+fn missing_file_fails() {
+    let output = Command::new(MDTABLEFMT)
+        .arg("does_not_exist.md")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("mdtablefmt: does_not_exist.md: "));
+}
+
+#[test]
+fn unknown_argument_fails() {
+    let output = Command::new(MDTABLEFMT).arg("--bogus").output().unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn check_lists_unformatted_tables() {
+    let input = "# Check\n\n| A   | B   |\n| --- | --- |\n\n|C|D|\n|-|-|\n";
+    let path = write_temp_file("check.md", input);
+
+    let output = Command::new(MDTABLEFMT)
+        .arg("--check")
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}:6-7: table is not formatted\n", path.display())
+    );
+    assert_eq!(fs::read_to_string(path).unwrap(), input);
+}
+
+#[test]
+fn check_passes_on_formatted_file() {
+    let path = write_temp_file("check_formatted.md", BASIC_TABLE_FIXED);
+
+    let output = Command::new(MDTABLEFMT)
+        .arg("--check")
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn diff_keeps_crlf_line_endings() {
+    let input = "|A|\r\n|-|\r\n\r\ntext";
+    let correct = "--- a/<stdin>\n+++ b/<stdin>\n@@ -1,4 +1,4 @@\n-|A|\r\n-|-|\r\n+| A   |\r\n+| --- |\r\n \r\n text\n\\ No newline at end of file\n";
+
+    let output = run_with_stdin(&["--diff"], input);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), correct);
+}
+
+#[test]
+fn diff_prints_unified_diff() {
+    let input = "# Diff\n\n|A|B|\n|-|-|\n\ntext\n";
+    let correct = "\
+--- a/<stdin>
++++ b/<stdin>
+@@ -1,6 +1,6 @@
+ # Diff
+ 
+-|A|B|
+-|-|-|
++| A   | B   |
++| --- | --- |
+ 
+ text
+";
+
+    let output = run_with_stdin(&["--diff"], input);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), correct);
+}
+
+#[test]
+fn sort_by_column_in_place() {
+    let input = "# Codes\n\n| Code | Name |\n| ---- | ---- |\n| E10  | b    |\n| E9   | a    |\n";
+    let correct = "# Codes\n\n| Code | Name |\n| ---- | ---- |\n| E9   | a    |\n| E10  | b    |\n";
+    let path = write_temp_file("sort.md", input);
+
+    let output = Command::new(MDTABLEFMT)
+        .args(["sort", "--column", "Code", "--by", "natural"])
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(path).unwrap(), correct);
+}
+
+#[test]
+fn sort_by_index_descending_from_stdin() {
+    let output = run_with_stdin(
+        &["sort", "--index", "2", "--descending"],
+        "|A|B|\n|-|-|\n|x|1|\n|y|2|\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "| A   | B   |\n| --- | --- |\n| y   | 2   |\n| x   | 1   |\n"
+    );
+}
+
+#[test]
+fn sort_check_lists_unsorted_tables() {
+    let input = "|A|\n|-|\n|a|\n|b|\n\n|A|\n|-|\n|b|\n|a|\n";
+    let output = run_with_stdin(&["sort", "--check", "--column", "A"], input);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "<stdin>:6-9: table is not sorted\n"
+    );
+}
+
+#[test]
+fn explicit_sort_overrides_sort_directive() {
+    let input = "<!-- table-fmt: sort=Name -->\n| Name | Size |\n| ---- | ---- |\n| a    | 2    |\n| b    | 1    |\n";
+    let correct = "<!-- table-fmt: sort=Name -->\n| Name | Size |\n| ---- | ---- |\n| b    | 1    |\n| a    | 2    |\n";
+    let path = write_temp_file("sort_directive.md", input);
+
+    let output = Command::new(MDTABLEFMT)
+        .args(["sort", "--column", "Size"])
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(&path).unwrap(), correct);
+
+    let output = Command::new(MDTABLEFMT)
+        .args(["sort", "--check", "--column", "Size"])
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn sort_requires_column() {
+    let output = run_with_stdin(&["sort", "--by", "numeric"], "");
+
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,71 @@
+use markdown_table_formatter::{extract_tables, format_tables, FormatOptions};
+use test_case::test_case;
+
+#[test_case(
+    "<!-- table-fmt: sort=Name -->\n|Name|Size|\n|-|-|\n|b|1|\n|a|2|",
+    "<!-- table-fmt: sort=Name -->\n| Name | Size |\n| ---- | ---- |\n| a    | 2    |\n| b    | 1    |";
+    "Sort"
+)]
+#[test_case(
+    "<!-- table-fmt: sort=Size:numeric:desc -->\n|Name|Size|\n|-|-|\n|a|9|\n|b|10|",
+    "<!-- table-fmt: sort=Size:numeric:desc -->\n| Name | Size |\n| ---- | ---- |\n| b    | 10   |\n| a    | 9    |";
+    "Sort with method and direction"
+)]
+#[test_case(
+    "<!-- table-fmt: align=Size:right,Name:center -->\n|Name|Size|\n|:-|-|\n|a|10|",
+    "<!-- table-fmt: align=Size:right,Name:center -->\n| Name  | Size |\n| :---: | ---: |\n|   a   |   10 |";
+    "Alignment override"
+)]
+#[test_case(
+    "<!-- table-fmt: min-width=\"File name\":10 -->\n|File name|B|\n|-|-|",
+    "<!-- table-fmt: min-width=\"File name\":10 -->\n| File name  | B   |\n| ---------- | --- |";
+    "Minimum width with quoted column"
+)]
+#[test_case(
+    "<!-- table-fmt: compact -->\n| A   |  B  |\n| --- | :-: |\n| a\\|b |  c  |",
+    "<!-- table-fmt: compact -->\n|A|B|\n|-|:-:|\n|a\\|b|c|";
+    "Compact"
+)]
+#[test_case(
+    "<!-- table-fmt: align=Time:decimal -->\n|Time|\n|:-:|\n|1.5|\n|12.25|\n|n/a|",
+    "<!-- table-fmt: align=Time:decimal -->\n| Time   |\n| ------ |\n|   1.5  |\n|  12.25 |\n| n/a    |";
+    "Decimal alignment"
+)]
+#[test_case(
+    "<!-- table-fmt: sort=Name -->\n\n> |Name|\n> |-|\n> |b|\n> |a|",
+    "<!-- table-fmt: sort=Name -->\n\n> | Name |\n> | ---- |\n> | b    |\n> | a    |";
+    "Only applies to the next sibling"
+)]
+#[test_case(
+    "<!-- table-fmt: sort=Missing align=Missing:right -->\n|A|\n|-|\n|b|\n|a|",
+    "<!-- table-fmt: sort=Missing align=Missing:right -->\n| A   |\n| --- |\n| b   |\n| a   |";
+    "Unknown column"
+)]
+fn applies_directives(doc: &str, correct: &str) {
+    assert_eq!(format_tables(doc), correct);
+    assert_eq!(format_tables(correct), correct);
+}
+
+#[test]
+fn only_next_table_is_affected() {
+    let doc = "<!-- table-fmt: compact -->\n| A |\n| - |\n\ntext\n\n|A|\n|-|\n";
+
+    assert_eq!(
+        format_tables(doc),
+        "<!-- table-fmt: compact -->\n|A|\n|-|\n\ntext\n\n| A   |\n| --- |\n"
+    );
+}
+
+#[test]
+fn structured_table_keeps_directives() {
+    let doc = "<!-- table-fmt: sort=A -->\n|A|\n|-|\n|b|\n|a|";
+    let mut table = extract_tables(doc).remove(0);
+
+    assert_eq!(table.rows, vec![vec!["b"], vec!["a"]]);
+
+    table.rows.push(vec![String::from("0")]);
+    assert_eq!(
+        table.to_markdown(&FormatOptions::default()),
+        "| A   |\n| --- |\n| 0   |\n| a   |\n| b   |"
+    );
+}
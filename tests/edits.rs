@@ -0,0 +1,81 @@
+use markdown_table_formatter::{
+    format_edits, format_edits_in_range, format_tables, format_tables_in_range, TextEdit,
+};
+use test_case::test_case;
+
+const INTERSPERSED_TABLES: &str = include_str!("text_snippets/interspersed_tables.txt");
+const INTERSPERSED_TABLES_FIXED: &str = include_str!("text_snippets/interspersed_tables_fixed.txt");
+const EMOJI_TABLE: &str = include_str!("text_snippets/emoji_table.txt");
+const TABLE_IN_LIST: &str = include_str!("text_snippets/table_in_list.txt");
+
+/// Apply `edits` by byte offset, starting from the end of the document.
+fn apply(doc: &str, edits: &[TextEdit]) -> String {
+    let mut applied = doc.to_string();
+
+    for edit in edits.iter().rev() {
+        applied.replace_range(edit.range(), &edit.new_text);
+    }
+
+    applied
+}
+
+#[test_case(INTERSPERSED_TABLES)]
+#[test_case(EMOJI_TABLE)]
+#[test_case(TABLE_IN_LIST)]
+fn applying_edits_matches_format_tables(doc: &str) {
+    assert_eq!(apply(doc, &format_edits(doc)), format_tables(doc));
+}
+
+#[test]
+fn formatted_document_has_no_edits() {
+    assert!(format_edits(INTERSPERSED_TABLES_FIXED).is_empty());
+}
+
+#[test]
+fn only_changed_tables_have_edits() {
+    let doc = "| A   |\n| --- |\n\n|B|\n|-|\n";
+
+    assert_eq!(
+        format_edits(doc),
+        vec![TextEdit {
+            start: 17,
+            end: 24,
+            utf16_start: 17,
+            utf16_end: 24,
+            new_text: String::from("| B   |\n| --- |"),
+        }]
+    );
+}
+
+#[test_case(0..0; "Cursor in first table")]
+#[test_case(2..4; "Selection in first table")]
+#[test_case(5..12; "Selection across both tables")]
+#[test_case(8..8; "Cursor between tables")]
+#[test_case(16..16; "Cursor at end of last table")]
+fn edits_in_range_match_format_tables_in_range(range: std::ops::Range<usize>) {
+    let doc = "|A|\n|-|\n\n|B|\n|-|";
+
+    assert_eq!(
+        apply(doc, &format_edits_in_range(doc, range.clone())),
+        format_tables_in_range(doc, range)
+    );
+}
+
+#[test]
+fn utf16_offsets() {
+    // 🦀 is 4 UTF-8 bytes and 2 UTF-16 code units, é is 2 UTF-8 bytes and 1 UTF-16 code unit
+    let doc = "🦀 é\n\n|🦀|\n|-|\n\n|B|\n|-|";
+    let edits = format_edits(doc);
+
+    assert_eq!(edits.len(), 2);
+    assert_eq!(edits[0].range(), 9..19);
+    assert_eq!(edits[0].utf16_range(), 6..14);
+    assert_eq!(edits[1].range(), 21..28);
+    assert_eq!(edits[1].utf16_range(), 16..23);
+
+    let utf16: Vec<u16> = doc.encode_utf16().collect();
+    assert_eq!(
+        String::from_utf16(&utf16[edits[0].utf16_range()]).unwrap(),
+        &doc[edits[0].range()]
+    );
+}
@@ -1,4 +1,7 @@
-use markdown_table_formatter::format_tables;
+use markdown_table_formatter::{
+    extract_tables, find_unformatted_tables, format_tables, format_tables_in_lines,
+    format_tables_in_range, format_tables_with, DelimiterStyle, FormatOptions, HeaderAlignment,
+};
 use test_case::test_case;
 
 const BASIC_TABLE: &str = include_str!("text_snippets/basic_table.txt");
@@ -31,8 +34,16 @@ const TABLE_WITH_WEIRD_UNICODE: &str = include_str!("text_snippets/weird_unicode
 const TABLE_WITH_WEIRD_UNICODE_FIXED: &str = include_str!("text_snippets/weird_unicode_fixed.txt"); // If you are unconvinced with the test, open up the fixed file in VIM
 const EMOJI_TABLE: &str = include_str!("text_snippets/emoji_table.txt");
 const EMOJI_TABLE_FIXED: &str = include_str!("text_snippets/emoji_table_fixed.txt");
+const TABLE_IN_LIST: &str = include_str!("text_snippets/table_in_list.txt");
+const TABLE_IN_LIST_FIXED: &str = include_str!("text_snippets/table_in_list_fixed.txt");
+const TABLE_IN_LIST_TABS: &str = include_str!("text_snippets/table_in_list_tabs.txt");
+const TABLE_IN_LIST_TABS_FIXED: &str = include_str!("text_snippets/table_in_list_tabs_fixed.txt");
+const TABLE_IN_FOOTNOTE: &str = include_str!("text_snippets/table_in_footnote.txt");
+const TABLE_IN_FOOTNOTE_FIXED: &str = include_str!("text_snippets/table_in_footnote_fixed.txt");
 const INTERSPERSED_TABLES: &str = include_str!("text_snippets/interspersed_tables.txt");
 const INTERSPERSED_TABLES_FIXED: &str = include_str!("text_snippets/interspersed_tables_fixed.txt");
+const IGNORE_REGIONS: &str = include_str!("text_snippets/ignore_regions.txt");
+const IGNORE_REGIONS_FIXED: &str = include_str!("text_snippets/ignore_regions_fixed.txt");
 
 #[test]
 fn crlf_test() {
@@ -42,6 +53,422 @@ fn crlf_test() {
     assert_eq!(format_tables(input), correct);
 }
 
+#[test]
+fn default_options_match_format_tables() {
+    for input in [
+        BASIC_TABLE,
+        TABLE_ALIGNMENT,
+        TABLE_IN_BLOCKQUOTE,
+        INTERSPERSED_TABLES,
+    ] {
+        assert_eq!(
+            format_tables_with(input, &FormatOptions::default()),
+            format_tables(input)
+        );
+    }
+}
+
+#[test]
+fn min_delimiter_dashes_option() {
+    let input = "|A|B|C|\n|:-|:-:|-|\n|C|D|E|";
+    let correct = "| A  |  B  | C |\n| :- | :-: | - |\n| C  |  D  | E |";
+    let options = FormatOptions::new().min_delimiter_dashes(1);
+
+    assert_eq!(format_tables_with(input, &options), correct);
+    assert_eq!(format_tables_with(correct, &options), correct);
+}
+
+#[test_case(
+    "| A | B |\n| :--- | :---: |\n| C | D |",
+    "|A|B|\n|:-|:-:|\n|C|D|";
+    "Alignment colons"
+)]
+#[test_case(
+    "|  a \\| b  |  `c\\|d`  |\n|---:|-|\n| | e |",
+    "|a \\| b|`c\\|d`|\n|-:|-|\n||e|";
+    "Escaped vertical bars and empty cells"
+)]
+#[test_case(
+    "> | A | B |\n> | - | - |\n> | C | D | E |\n> F",
+    "> |A|B|\n> |-|-|\n> |C|D|E|\n> |F|";
+    "Blockquote with extra cells"
+)]
+fn compact_option(input: &str, correct: &str) {
+    let options = FormatOptions::new().compact(true);
+
+    assert_eq!(format_tables_with(input, &options), correct);
+    assert_eq!(format_tables_with(correct, &options), correct);
+}
+
+#[test_case(
+    "| Name | Size |\n| :- | -: |\n| a.txt | 12 |",
+    "Name  | Size\n:---- | ---:\na.txt |   12";
+    "Without outer pipes"
+)]
+#[test_case(
+    "> |A|B|\n> |-|-|\n> |C|D|",
+    "> A   | B\n> --- | ---\n> C   | D";
+    "Blockquote"
+)]
+#[test_case(
+    "A|B\n-|-\nC|D",
+    "A   | B\n--- | ---\nC   | D";
+    "Already without outer pipes"
+)]
+#[test_case(
+    "|A|\n|-|\n|B|",
+    "| A   |\n| --- |\n| B   |";
+    "Single column"
+)]
+#[test_case(
+    "|A|B|\n|-|-|\n|C||",
+    "| A   | B   |\n| --- | --- |\n| C   |     |";
+    "Empty last cell"
+)]
+#[test_case(
+    "|A|B|\n|-|-|\n||D|",
+    "| A   | B   |\n| --- | --- |\n|     | D   |";
+    "Empty first cell"
+)]
+#[test_case(
+    "|A|B|\n|-|-|\n|C|",
+    "| A   | B   |\n| --- | --- |\n| C   |";
+    "Missing cell"
+)]
+#[test_case(
+    "|A|B|\n|-|-|\n|- C|D|",
+    "| A   | B   |\n| --- | --- |\n| - C | D   |";
+    "List marker"
+)]
+#[test_case(
+    "|A|B|\n|-|-|\n|1. C|D|",
+    "| A    | B   |\n| ---- | --- |\n| 1. C | D   |";
+    "Ordered list marker"
+)]
+#[test_case(
+    "|A|B|\n|-|-|\n|1.5|D|",
+    "A   | B\n--- | ---\n1.5 | D";
+    "Number that is not an ordered list marker"
+)]
+fn outer_pipes_option(input: &str, correct: &str) {
+    let options = FormatOptions::new().outer_pipes(false);
+
+    assert_eq!(format_tables_with(input, &options), correct);
+    assert_eq!(format_tables_with(correct, &options), correct);
+}
+
+#[test_case(
+    "|N|x|\n|--:|---|\n|1|a|\n|123456|b|",
+    FormatOptions::new();
+    "Right aligned"
+)]
+#[test_case(
+    "|N|x|\n|:-:|---|\n|1|a|\n|1234567890|b|",
+    FormatOptions::new();
+    "Centered"
+)]
+#[test_case(
+    "|N|x|\n|---|---|\n|1.5|a|\n|12345.25|b|",
+    FormatOptions::new().decimal_alignment(0);
+    "Decimal"
+)]
+fn outer_pipes_kept_before_indented_code_block(input: &str, options: FormatOptions) {
+    let options = options.outer_pipes(false);
+    let formatted = format_tables_with(input, &options);
+
+    // the same text as with outer vertical bars
+    assert_eq!(
+        formatted,
+        format_tables_with(input, &options.outer_pipes(true))
+    );
+
+    let tables = extract_tables(&formatted);
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].rows.len(), 2);
+}
+
+#[test_case("|A|B|\n|-:|-|\n|CC|D|", "|    A | B   |\n| ---: | --- |\n|   CC | D   |"; "Right aligned")]
+#[test_case("|A|B|\n|:-:|-|\n|CCC|D|", "|   A   | B   |\n| :---: | --- |\n|  CCC  | D   |"; "Centered")]
+fn outer_pipes_kept_before_leading_whitespace(input: &str, correct: &str) {
+    let options = FormatOptions::new().outer_pipes(false);
+    let formatted = format_tables_with(input, &options);
+
+    assert_eq!(formatted, correct);
+    assert_eq!(format_tables_with(&formatted, &options), formatted);
+    assert_eq!(extract_tables(&formatted)[0].rows.len(), 1);
+}
+
+#[test_case(
+    "1. |:12#[^1]: :\n a|    \n| abc-    \n    |- ",
+    "1. |:12#[^1]: :\n a|    \n| abc- |\n    | ---- |";
+    "Column inside cell"
+)]
+#[test_case(
+    "1. a|\n| é |\n    |-|",
+    "1. a|\n| é   |\n    | --- |";
+    "Column inside character"
+)]
+#[test_case(
+    "- x\n|a|\n  |-|\n  |b|\n",
+    "- x\n| a   |\n  | --- |\n  | b   |\n";
+    "Body row in list item"
+)]
+fn header_row_as_lazy_list_continuation(input: &str, correct: &str) {
+    assert_eq!(format_tables(input), correct);
+    assert_eq!(format_tables(correct), correct);
+}
+
+#[test]
+fn compact_without_outer_pipes() {
+    let options = FormatOptions::new().compact(true).outer_pipes(false);
+    let correct = "A|B\n:-|-:\nC|D";
+
+    assert_eq!(
+        format_tables_with("| A | B |\n|:-|-:|\n| C | D |", &options),
+        correct
+    );
+    assert_eq!(format_tables_with(correct, &options), correct);
+}
+
+#[test_case(
+    0,
+    "|Name|Size|Kind|\n|:-|-:|:-:|\n|a.txt|12|text|",
+    "|Name |Size|Kind |\n|:----|---:|:---:|\n|a.txt|  12|text |";
+    "No padding"
+)]
+#[test_case(
+    2,
+    "|Name|Size|Kind|\n|:-|-:|:-:|\n|a.txt|12|text|",
+    "|  Name   |  Size  |  Kind   |\n|  :----  |  ---:  |  :---:  |\n|  a.txt  |    12  |  text   |";
+    "Two spaces"
+)]
+#[test_case(
+    0,
+    "|A|B|\n|-|-|\n||D|",
+    "|A  |B  |\n|---|---|\n|   |D  |";
+    "No padding with empty cell"
+)]
+fn cell_padding_option(padding: usize, input: &str, correct: &str) {
+    let options = FormatOptions::new().cell_padding(padding);
+
+    assert_eq!(format_tables_with(input, &options), correct);
+    assert_eq!(format_tables_with(correct, &options), correct);
+}
+
+#[test_case(0, "Name |Size\n:----|---:\na.txt|  12"; "No padding")]
+#[test_case(2, "Name   |  Size\n:----  |  ---:\na.txt  |    12"; "Two spaces")]
+fn cell_padding_without_outer_pipes(padding: usize, correct: &str) {
+    let options = FormatOptions::new()
+        .cell_padding(padding)
+        .outer_pipes(false);
+
+    assert_eq!(
+        format_tables_with("|Name|Size|\n|:-|-:|\n|a.txt|12|", &options),
+        correct
+    );
+    assert_eq!(format_tables_with(correct, &options), correct);
+}
+
+#[test_case(
+    DelimiterStyle::Minimal,
+    "| Name  | Size | Kind |\n|:---|---:|:---:|\n| a.txt |   12 | text |";
+    "Minimal"
+)]
+#[test_case(
+    DelimiterStyle::Filled,
+    "| Name  | Size | Kind  |\n|:------|-----:|:-----:|\n| a.txt |   12 | text  |";
+    "Filled"
+)]
+fn delimiter_style_option(style: DelimiterStyle, correct: &str) {
+    let options = FormatOptions::new().delimiter_style(style);
+
+    assert_eq!(
+        format_tables_with("|Name|Size|Kind|\n|:-|-:|:-:|\n|a.txt|12|text|", &options),
+        correct
+    );
+    assert_eq!(format_tables_with(correct, &options), correct);
+}
+
+#[test_case(DelimiterStyle::Padded, "Name  | Size\n:---- | ---:\na.txt |   12"; "Padded")]
+#[test_case(DelimiterStyle::Minimal, "Name  | Size\n:---|---:\na.txt |   12"; "Minimal")]
+#[test_case(DelimiterStyle::Filled, "Name  | Size\n:-----|-----:\na.txt |   12"; "Filled")]
+fn delimiter_style_without_outer_pipes(style: DelimiterStyle, correct: &str) {
+    let options = FormatOptions::new()
+        .delimiter_style(style)
+        .outer_pipes(false);
+
+    assert_eq!(
+        format_tables_with("|Name|Size|\n|:-|-:|\n|a.txt|12|", &options),
+        correct
+    );
+    assert_eq!(format_tables_with(correct, &options), correct);
+}
+
+#[test_case(DelimiterStyle::Padded)]
+#[test_case(DelimiterStyle::Minimal)]
+#[test_case(DelimiterStyle::Filled)]
+fn delimiter_style_idempotency(style: DelimiterStyle) {
+    for padding in [0, 1, 2] {
+        let options = FormatOptions::new()
+            .delimiter_style(style)
+            .cell_padding(padding);
+
+        for input in [
+            BASIC_TABLE,
+            TABLE_ALIGNMENT,
+            TABLE_WIDTH_EXPANSION,
+            TABLE_WIDTH_REDUCTION,
+            TABLE_WITH_EMPTY_CELLS,
+            TABLE_WITH_PIPE_IN_CODE_BLOCK,
+            TABLE_WITH_TRAILING_TEXT,
+            TWO_ROW_TABLE,
+            TABLE_IN_BLOCKQUOTE,
+            TABLE_WITH_WEIRD_UNICODE,
+            EMOJI_TABLE,
+            INTERSPERSED_TABLES,
+            TABLE_IN_LIST,
+            TABLE_IN_FOOTNOTE,
+            IGNORE_REGIONS,
+        ] {
+            let formatted = format_tables_with(input, &options);
+
+            assert_eq!(format_tables_with(&formatted, &options), formatted);
+            assert!(find_unformatted_tables(&formatted, &options).is_empty());
+        }
+    }
+}
+
+#[test_case(
+    "|1|2|\n|:-|:-|\n|1|2|3|\n|1|2|333|4|",
+    "| 1    | 2    |\n| :--- | :--- |\n| 1    | 2    | 3   |\n| 1    | 2    | 333 | 4 |";
+    "Extra columns"
+)]
+#[test_case(
+    "|A|\n|-|\n|B|C|\n|D|EE|",
+    "| A   |\n| --- |\n| B   | C  |\n| D   | EE |";
+    "Single column"
+)]
+fn align_extra_columns_option(input: &str, correct: &str) {
+    let options = FormatOptions::new().align_extra_columns(true);
+
+    assert_eq!(format_tables_with(input, &options), correct);
+    assert_eq!(format_tables_with(correct, &options), correct);
+}
+
+#[test_case(
+    "|A|B|C|\n|-|:-:|-|\n|D|\n|E|F|G|",
+    "| A   |   B   | C   |\n| --- | :---: | --- |\n| D   |       |     |\n| E   |   F   | G   |";
+    "Short row"
+)]
+#[test_case(
+    "> |A|B|\n> |-|-|\n> |C|D|E|\n> |F|",
+    "> | A   | B   |     |\n> | --- | --- | --- |\n> | C   | D   | E   |\n> | F   |     |     |";
+    "Extra cells in blockquote"
+)]
+#[test_case(
+    "|A|B|\n|-|-|\n|C|D|",
+    "| A   | B   |\n| --- | --- |\n| C   | D   |";
+    "Nothing to repair"
+)]
+fn repair_option(input: &str, correct: &str) {
+    let options = FormatOptions::new().repair(true);
+
+    assert_eq!(format_tables_with(input, &options), correct);
+    assert_eq!(format_tables_with(correct, &options), correct);
+}
+
+#[test_case(
+    "|Benchmark|Time|\n|-|-|\n|a|1.5|\n|b|12.25|\n|c|100|\n|d||\n|e|-0.125 ms|",
+    "| Benchmark | Time       |\n| --------- | ---------- |\n| a         |   1.5      |\n| b         |  12.25     |\n| c         | 100        |\n| d         |            |\n| e         |  -0.125 ms |";
+    "No alignment"
+)]
+#[test_case(
+    "|Benchmark|Time|\n|:-|:-:|\n|a|1.5|\n|b|12.25|",
+    "| Benchmark | Time  |\n| :-------- | ----- |\n| a         |  1.5  |\n| b         | 12.25 |";
+    "Center alignment becomes no alignment"
+)]
+fn decimal_alignment_option(input: &str, correct: &str) {
+    let options = FormatOptions::new().decimal_alignment(1);
+
+    assert_eq!(format_tables_with(input, &options), correct);
+    assert_eq!(format_tables_with(correct, &options), correct);
+}
+
+#[test_case(
+    "|Name|Size|Ratio|\n|-|-|-|\n|a|1,024|50%|\n|b|12||",
+    "| Name |  Size | Ratio |\n| ---- | ----: | ----: |\n| a    | 1,024 |   50% |\n| b    |    12 |       |";
+    "Numeric columns"
+)]
+#[test_case(
+    "|Name|Size|\n|-|-|\n|a|1|\n|b|n/a|",
+    "| Name | Size |\n| ---- | ---- |\n| a    | 1    |\n| b    | n/a  |";
+    "Mixed column"
+)]
+#[test_case(
+    "|Name|Size|\n|-|:-:|\n|a|1|\n|b|2|",
+    "| Name | Size  |\n| ---- | :---: |\n| a    |   1   |\n| b    |   2   |";
+    "Explicit alignment"
+)]
+#[test_case(
+    "|Name|Size|\n|-|-|\n|a||",
+    "| Name | Size |\n| ---- | ---- |\n| a    |      |";
+    "Empty column"
+)]
+#[test_case(
+    "<!-- table-fmt: align=Size:none -->\n|Name|Size|\n|-|-|\n|a|1|",
+    "<!-- table-fmt: align=Size:none -->\n| Name | Size |\n| ---- | ---- |\n| a    | 1    |";
+    "Directive takes precedence"
+)]
+fn infer_alignment_option(input: &str, correct: &str) {
+    let options = FormatOptions::new().infer_alignment(true);
+
+    assert_eq!(format_tables_with(input, &options), correct);
+    assert_eq!(format_tables_with(correct, &options), correct);
+}
+
+#[test_case(HeaderAlignment::Column, "| N     |      Size |"; "Column")]
+#[test_case(HeaderAlignment::Left, "| N     | Size      |"; "Left")]
+#[test_case(HeaderAlignment::Center, "|   N   |   Size    |"; "Center")]
+#[test_case(HeaderAlignment::Right, "|     N |      Size |"; "Right")]
+fn header_alignment_option(alignment: HeaderAlignment, header: &str) {
+    let options = FormatOptions::new().header_alignment(alignment);
+    let correct = format!("{header}\n| :---- | --------: |\n| a.txt | 1,024,000 |");
+
+    assert_eq!(
+        format_tables_with("|N|Size|\n|:-|-:|\n|a.txt|1,024,000|", &options),
+        correct
+    );
+    assert_eq!(format_tables_with(&correct, &options), correct);
+}
+
+#[test_case(HeaderAlignment::Center, "|  A  |  B  |\n| --- | --- |\n| C   | D   |"; "Center")]
+#[test_case(HeaderAlignment::Right, "|   A |   B |\n| --- | --- |\n| C   | D   |"; "Right")]
+fn header_alignment_without_outer_pipes(alignment: HeaderAlignment, correct: &str) {
+    let options = FormatOptions::new()
+        .outer_pipes(false)
+        .header_alignment(alignment);
+
+    assert_eq!(format_tables_with("|A|B|\n|-|-|\n|C|D|", &options), correct);
+    assert_eq!(format_tables_with(correct, &options), correct);
+    assert_eq!(extract_tables(correct).len(), 1);
+}
+
+#[test]
+fn find_unformatted_tables_line_ranges() {
+    let options = FormatOptions::default();
+
+    assert_eq!(
+        find_unformatted_tables(INTERSPERSED_TABLES, &options),
+        vec![3..=5, 13..=16, 18..=21]
+    );
+    assert!(find_unformatted_tables(INTERSPERSED_TABLES_FIXED, &options).is_empty());
+
+    assert_eq!(
+        find_unformatted_tables(IGNORE_REGIONS, &options),
+        vec![3..=4, 17..=18, 25..=27]
+    );
+}
+
 #[test_case(BASIC_TABLE, BASIC_TABLE_FIXED)]
 #[test_case(TABLE_ALIGNMENT, TABLE_ALIGNMENT_FIXED)]
 #[test_case(TABLE_IN_CODE_BLOCK, TABLE_IN_CODE_BLOCK)]
@@ -56,6 +483,10 @@ fn crlf_test() {
 #[test_case(TABLE_WITH_WEIRD_UNICODE, TABLE_WITH_WEIRD_UNICODE_FIXED)]
 #[test_case(EMOJI_TABLE, EMOJI_TABLE_FIXED)]
 #[test_case(INTERSPERSED_TABLES, INTERSPERSED_TABLES_FIXED)]
+#[test_case(TABLE_IN_LIST, TABLE_IN_LIST_FIXED)]
+#[test_case(TABLE_IN_LIST_TABS, TABLE_IN_LIST_TABS_FIXED)]
+#[test_case(TABLE_IN_FOOTNOTE, TABLE_IN_FOOTNOTE_FIXED)]
+#[test_case(IGNORE_REGIONS, IGNORE_REGIONS_FIXED)]
 fn test_table_formatter(preformatted: &str, correct: &str) {
     let formatted = format_tables(preformatted);
     assert_eq!(formatted, correct);
@@ -63,3 +494,38 @@ fn test_table_formatter(preformatted: &str, correct: &str) {
     // idempotency test
     assert_eq!(format_tables(correct), correct);
 }
+
+#[test]
+fn format_tables_in_range_only_touches_overlapping_tables() {
+    let doc = "|A|\n|-|\n\ntext\n\n|B|\n|-|\n\n|C|\n|-|\n";
+
+    assert_eq!(
+        format_tables_in_range(doc, 17..22),
+        "|A|\n|-|\n\ntext\n\n| B   |\n| --- |\n\n|C|\n|-|\n"
+    );
+
+    // a cursor at the end of a table selects it
+    assert_eq!(
+        format_tables_in_range(doc, 7..7),
+        "| A   |\n| --- |\n\ntext\n\n|B|\n|-|\n\n|C|\n|-|\n"
+    );
+
+    // a selection ending at the start of a table does not select it
+    assert_eq!(format_tables_in_range(doc, 9..15), doc);
+
+    assert_eq!(
+        format_tables_in_range(doc, 0..doc.len()),
+        format_tables(doc)
+    );
+}
+
+#[test]
+fn format_tables_in_lines_only_touches_overlapping_tables() {
+    let doc = "|A|\n|-|\n\ntext\n\n|B|\n|-|\n\n|C|\n|-|\n";
+
+    assert_eq!(
+        format_tables_in_lines(doc, 2..=6),
+        "| A   |\n| --- |\n\ntext\n\n| B   |\n| --- |\n\n|C|\n|-|\n"
+    );
+    assert_eq!(format_tables_in_lines(doc, 3..=5), doc);
+}
@@ -0,0 +1,125 @@
+use markdown_table_formatter::{
+    find_repairs, format_tables_with, lint_tables, Diagnostic, FormatOptions, Position, Rule,
+    Severity,
+};
+
+const TABLE_WITH_EMPTY_CELLS: &str = include_str!("text_snippets/table_with_empty_cells.txt");
+const BASIC_TABLE_FIXED: &str = include_str!("text_snippets/basic_table_fixed.txt");
+const TABLE_IN_LIST_TABS: &str = include_str!("text_snippets/table_in_list_tabs.txt");
+
+fn rules(diagnostics: &[Diagnostic]) -> Vec<(&'static str, usize)> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.rule.id(), diagnostic.start.line))
+        .collect()
+}
+
+#[test]
+fn well_formed_table_has_no_diagnostics() {
+    assert!(lint_tables(BASIC_TABLE_FIXED).is_empty());
+}
+
+#[test]
+fn extra_cells() {
+    let doc = "| 1 | 2 |\n| - | - |\n| 1 | 2 | 3 | 4 |";
+    let diagnostics = lint_tables(doc);
+
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic {
+            rule: Rule::ExtraCells,
+            severity: Severity::Warning,
+            message: String::from(
+                "row has 2 more cell(s) than the delimiter row, which will not be rendered"
+            ),
+            range: 29..36,
+            start: Position {
+                line: 3,
+                column: 10
+            },
+            end: Position {
+                line: 3,
+                column: 17
+            },
+        }]
+    );
+    assert_eq!(&doc[diagnostics[0].range.clone()], " 3 | 4 ");
+}
+
+#[test]
+fn missing_cells() {
+    let doc = "Text\n\n> | 1 | 2 | 3 |\n> | - | - | - |\n> | 1 | 2\n";
+    let diagnostics = lint_tables(doc);
+
+    assert_eq!(rules(&diagnostics), vec![("missing-cells", 5)]);
+    assert_eq!(&doc[diagnostics[0].range.clone()], "| 1 | 2");
+    assert_eq!(diagnostics[0].start, Position { line: 5, column: 3 });
+}
+
+#[test]
+fn empty_header_cell() {
+    let doc = "- | A | |\n  |-|-|\n  | B | C |";
+    let diagnostics = lint_tables(doc);
+
+    assert_eq!(rules(&diagnostics), vec![("empty-header-cell", 1)]);
+    assert_eq!(diagnostics[0].severity, Severity::Information);
+    assert_eq!(diagnostics[0].start, Position { line: 1, column: 8 });
+}
+
+#[test]
+fn row_without_pipes() {
+    let doc = "| A | B |\n|-|-|\n| C | D |\nThis was meant to be a paragraph\n";
+    let diagnostics = lint_tables(doc);
+
+    assert_eq!(rules(&diagnostics), vec![("row-without-pipes", 4)]);
+    assert_eq!(
+        &doc[diagnostics[0].range.clone()],
+        "This was meant to be a paragraph"
+    );
+}
+
+#[test]
+fn tab_indentation_is_not_a_cell() {
+    assert_eq!(rules(&lint_tables(TABLE_IN_LIST_TABS)), vec![]);
+}
+
+#[test]
+fn header_row_as_lazy_list_continuation() {
+    // `comrak` reports the column of the list item content, which is inside `é` on the header row
+    assert_eq!(rules(&lint_tables("1. a|\n| é |\n    |-|")), vec![]);
+}
+
+#[test]
+fn empty_cells_are_not_missing_cells() {
+    assert_eq!(rules(&lint_tables(TABLE_WITH_EMPTY_CELLS)), vec![]);
+}
+
+#[test]
+fn repairs() {
+    let doc = "Text\n\n> | A | B |\n> |-|-|\n> | C | D | E |\n> | F\n";
+    let diagnostics = find_repairs(doc);
+
+    assert_eq!(
+        rules(&diagnostics),
+        vec![
+            ("missing-cells", 3),
+            ("extra-cells", 4),
+            ("missing-cells", 6)
+        ]
+    );
+    assert_eq!(diagnostics[0].message, "added 1 empty cell(s)");
+    assert_eq!(
+        diagnostics[1].message,
+        "added 1 delimiter cell(s) so that every cell is rendered"
+    );
+    assert_eq!(diagnostics[2].message, "added 2 empty cell(s)");
+    assert_eq!(&doc[diagnostics[2].range.clone()], "| F");
+
+    let repaired = format_tables_with(doc, &FormatOptions::new().repair(true));
+    assert!(find_repairs(&repaired).is_empty());
+    // the header cell added by the repair is empty
+    assert_eq!(
+        rules(&lint_tables(&repaired)),
+        vec![("empty-header-cell", 3)]
+    );
+}
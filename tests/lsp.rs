@@ -0,0 +1,222 @@
+//! Drive `mdtablefmt-lsp` with a scripted JSON-RPC client.
+
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+const URI: &str = "file:///test.md";
+
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Client {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_mdtablefmt-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+
+        Client {
+            child,
+            stdin,
+            stdout,
+            next_id: 1,
+        }
+    }
+
+    fn send(&mut self, message: Value) {
+        self.send_body(&message.to_string());
+    }
+
+    fn send_body(&mut self, body: &str) {
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut content_length = 0;
+
+        loop {
+            let mut line = String::new();
+            self.stdout.read_line(&mut line).unwrap();
+
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+
+            if let Some(value) = line.strip_prefix("Content-Length: ") {
+                content_length = value.parse().unwrap();
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        self.stdout.read_exact(&mut body).unwrap();
+
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    /// Send a request and return its response.
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+
+        let response = self.receive();
+        assert_eq!(response["id"], id);
+
+        response
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    /// Initialize the server and open `text` as `URI`, returning the published diagnostics.
+    fn open(text: &str) -> (Self, Value) {
+        let mut client = Client::start();
+
+        let response = client.request("initialize", json!({ "capabilities": {} }));
+        assert_eq!(
+            response["result"]["capabilities"]["documentFormattingProvider"],
+            true
+        );
+        client.notify("initialized", json!({}));
+
+        client.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": { "uri": URI, "languageId": "markdown", "version": 1, "text": text }
+            }),
+        );
+
+        let diagnostics = client.receive();
+        assert_eq!(diagnostics["method"], "textDocument/publishDiagnostics");
+
+        (client, diagnostics["params"]["diagnostics"].clone())
+    }
+
+    fn shutdown(mut self) {
+        let response = self.request("shutdown", Value::Null);
+        assert_eq!(response["result"], Value::Null);
+
+        self.notify("exit", Value::Null);
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+#[test]
+fn formatting() {
+    let (mut client, diagnostics) = Client::open("# 🦀\n\n|A|B|\n|-|-|\n");
+    assert_eq!(diagnostics, json!([]));
+
+    let response = client.request(
+        "textDocument/formatting",
+        json!({
+            "textDocument": { "uri": URI },
+            "options": { "tabSize": 4, "insertSpaces": true }
+        }),
+    );
+
+    assert_eq!(
+        response["result"],
+        json!([{
+            "range": {
+                "start": { "line": 2, "character": 0 },
+                "end": { "line": 3, "character": 5 }
+            },
+            "newText": "| A   | B   |\n| --- | --- |"
+        }])
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn range_formatting_only_touches_selected_tables() {
+    let (mut client, _) = Client::open("|A|\n|-|\n\ntext\n\n|B|\n|-|\n");
+
+    let response = client.request(
+        "textDocument/rangeFormatting",
+        json!({
+            "textDocument": { "uri": URI },
+            "range": {
+                "start": { "line": 5, "character": 1 },
+                "end": { "line": 5, "character": 1 }
+            },
+            "options": { "tabSize": 4, "insertSpaces": true }
+        }),
+    );
+
+    let edits = response["result"].as_array().unwrap();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0]["newText"], "| B   |\n| --- |");
+
+    client.shutdown();
+}
+
+#[test]
+fn diagnostics_are_pushed_on_open_and_change() {
+    let (mut client, diagnostics) = Client::open("| A | B |\n|-|-|\n| C | D | E |\n");
+
+    assert_eq!(
+        diagnostics,
+        json!([{
+            "range": {
+                "start": { "line": 2, "character": 9 },
+                "end": { "line": 2, "character": 12 }
+            },
+            "severity": 2,
+            "code": "extra-cells",
+            "source": "markdown-table-formatter",
+            "message": "row has 1 more cell(s) than the delimiter row, which will not be rendered"
+        }])
+    );
+
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": URI, "version": 2 },
+            "contentChanges": [{ "text": "| A | B |\n|-|-|\n| C | D |\n" }]
+        }),
+    );
+
+    let notification = client.receive();
+    assert_eq!(notification["method"], "textDocument/publishDiagnostics");
+    assert_eq!(notification["params"]["diagnostics"], json!([]));
+
+    client.shutdown();
+}
+
+#[test]
+fn malformed_json_is_a_parse_error() {
+    let (mut client, _) = Client::open("");
+
+    client.send_body("{\"jsonrpc\": \"2.0\", \"id\": 5,");
+
+    let response = client.receive();
+    assert_eq!(response["id"], Value::Null);
+    assert_eq!(response["error"]["code"], -32700);
+
+    // the server keeps serving
+    client.shutdown();
+}
+
+#[test]
+fn unknown_method() {
+    let (mut client, _) = Client::open("");
+
+    let response = client.request("textDocument/hover", json!({}));
+    assert_eq!(response["error"]["code"], -32601);
+
+    client.shutdown();
+}
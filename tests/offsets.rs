@@ -0,0 +1,72 @@
+use markdown_table_formatter::{format_tables, map_offsets, map_utf16_offsets};
+use test_case::test_case;
+
+/// Return the formatted document with `^` inserted at each mapped offset.
+///
+/// `doc` marks each offset to map with `^`.
+fn map_markers(doc: &str) -> String {
+    let mut offsets = Vec::new();
+    let mut unmarked = String::new();
+
+    for character in doc.chars() {
+        if character == '^' {
+            offsets.push(unmarked.len());
+        } else {
+            unmarked.push(character);
+        }
+    }
+
+    let mut formatted = format_tables(&unmarked);
+    for offset in map_offsets(&unmarked, &offsets).into_iter().rev() {
+        formatted.insert(offset, '^');
+    }
+
+    formatted
+}
+
+#[test_case("te^xt\n\n|A|B|\n|-|-|", "te^xt\n\n| A   | B   |\n| --- | --- |"; "Before table")]
+#[test_case("|A|B|\n|-|-|\n\nte^xt", "| A   | B   |\n| --- | --- |\n\nte^xt"; "After table")]
+#[test_case("|Na^me|B|\n|-|-|", "| Na^me | B   |\n| ---- | --- |"; "Inside cell content")]
+#[test_case("|A|  ^B|\n|-|-|", "| A   | ^B   |\n| --- | --- |"; "Leading whitespace")]
+#[test_case("|A|B  ^|\n|-|-|", "| A   | B^   |\n| --- | --- |"; "Trailing whitespace")]
+#[test_case("|A|B^\n|-|-|", "| A   | B^   |\n| --- | --- |"; "End of row without trailing bar")]
+#[test_case("^|A|B|\n|-|-|", "^| A   | B   |\n| --- | --- |"; "Before leading bar")]
+#[test_case("|A|B|^\n|-|-|", "| A   | B   |^\n| --- | --- |"; "After trailing bar")]
+#[test_case("|A|B|\n|-|-|\n|CCCCC|d^d|", "| A     | B   |\n| ----- | --- |\n| CCCCC | d^d  |"; "Body row")]
+#[test_case("> |A|B|\n> |-|-|\n> |C|^|", "> | A   | B   |\n> | --- | --- |\n> | C   | ^    |"; "Empty cell in blockquote")]
+#[test_case("- ^|A|\n  |-|", "- ^| A   |\n  | --- |"; "List item marker")]
+#[test_case("<!-- table-fmt: sort=N -->\n|N|V|\n|-|-|\n|ze^ta|1|\n|alpha|2|\n", "<!-- table-fmt: sort=N -->\n| N     | V   |\n| ----- | --- |\n| alpha | 2   |\n| ze^ta  | 1   |\n"; "Row sorted by directive")]
+fn offsets_follow_cells(doc: &str, correct: &str) {
+    assert_eq!(map_markers(doc), correct);
+}
+
+#[test]
+fn utf16_offsets() {
+    let doc = "🦀\n\n|🦀|x|\n|-|-|\n\n🦀";
+    let formatted = format_tables(doc);
+
+    // after "x", and after the last crab
+    let utf16_offsets = [9, 20];
+    let mapped = map_utf16_offsets(doc, &utf16_offsets);
+
+    let formatted_utf16: Vec<u16> = formatted.encode_utf16().collect();
+    assert_eq!(
+        String::from_utf16(&formatted_utf16[..mapped[0]]).unwrap(),
+        "🦀\n\n| 🦀  | x"
+    );
+    assert_eq!(mapped[1], formatted_utf16.len());
+}
+
+#[test_case("1. a|\n| é |\n    |-|"; "Header row as lazy list continuation")]
+#[test_case("1. |:12#é[^1]: :\n a|    \n| é-    \n    |- "; "Footnote in list item")]
+fn offsets_inside_characters_of_container_column(doc: &str) {
+    // `comrak` reports the column of the list item content, which is inside `é` on the header row
+    let formatted = format_tables(doc);
+    let offsets: Vec<usize> = (0..=doc.len())
+        .filter(|&offset| doc.is_char_boundary(offset))
+        .collect();
+
+    for offset in map_offsets(doc, &offsets) {
+        assert!(formatted.is_char_boundary(offset));
+    }
+}
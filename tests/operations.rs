@@ -0,0 +1,67 @@
+use markdown_table_formatter::{edit_table, TableOperation};
+use test_case::test_case;
+
+const TABLE: &str = "# Title\n\n| A | B |\n|:-|-:|\n| C | D |\n| E | F |\n";
+
+/// Apply `operation` at the `^` in `doc` and return the new document.
+fn apply(doc: &str, operation: TableOperation) -> Option<String> {
+    let offset = doc.find('^').unwrap();
+    let doc = doc.replacen('^', "", 1);
+
+    edit_table(&doc, offset, operation).map(|edit| {
+        let mut new_doc = doc.clone();
+        new_doc.replace_range(edit.range(), &edit.new_text);
+        new_doc
+    })
+}
+
+#[test_case("| A | B |\n|:-|-:|\n| ^C | D |\n| E | F |", TableOperation::InsertRowAbove, "| A    |    B |\n| :--- | ---: |\n|      |      |\n| C    |    D |\n| E    |    F |"; "Insert row above")]
+#[test_case("| A | B |\n|:-|-:|\n| ^C | D |\n| E | F |", TableOperation::InsertRowBelow, "| A    |    B |\n| :--- | ---: |\n| C    |    D |\n|      |      |\n| E    |    F |"; "Insert row below")]
+#[test_case("| ^A | B |\n|:-|-:|\n| C | D |", TableOperation::InsertRowBelow, "| A    |    B |\n| :--- | ---: |\n|      |      |\n| C    |    D |"; "Insert row below header")]
+#[test_case("| A | B |\n|:-|-:|\n| C | D |\n| E^ | F |", TableOperation::DeleteRow, "| A    |    B |\n| :--- | ---: |\n| C    |    D |"; "Delete row")]
+#[test_case("| A | B |\n|:-|-:|\n| C | D |\n| E^ | F |", TableOperation::MoveRowUp, "| A    |    B |\n| :--- | ---: |\n| E    |    F |\n| C    |    D |"; "Move row up")]
+#[test_case("| A | B |\n|:-|-:|\n| C^ | D |\n| E | F |", TableOperation::MoveRowDown, "| A    |    B |\n| :--- | ---: |\n| E    |    F |\n| C    |    D |"; "Move row down")]
+#[test_case("| A | B^ |\n|:-|-:|\n| C | D |", TableOperation::InsertColumnLeft, "| A    |     |    B |\n| :--- | --- | ---: |\n| C    |     |    D |"; "Insert column left")]
+#[test_case("| A | B |\n|:^-|-:|\n| C | D |", TableOperation::InsertColumnRight, "| A    |     |    B |\n| :--- | --- | ---: |\n| C    |     |    D |"; "Insert column right from delimiter row")]
+#[test_case("| A | B |\n|:-|-:|\n| C | ^D |", TableOperation::DeleteColumn, "| A    |\n| :--- |\n| C    |"; "Delete column")]
+#[test_case("| A | B |\n|:-|-:|\n| C | D^ |", TableOperation::MoveColumnLeft, "|    B | A    |\n| ---: | :--- |\n|    D | C    |"; "Move column left")]
+#[test_case("^| A | B |\n|:-|-:|\n| C | D |", TableOperation::MoveColumnRight, "|    B | A    |\n| ---: | :--- |\n|    D | C    |"; "Move column right from leading bar")]
+#[test_case("<!-- table-fmt: sort=A -->\n| A | B |\n|:-|-:|\n| C^ | D |\n| E | F |", TableOperation::MoveRowDown, "<!-- table-fmt: sort=A -->\n| A    |    B |\n| :--- | ---: |\n| E    |    F |\n| C    |    D |"; "Move row down under sort directive")]
+#[test_case("<!-- table-fmt: sort=A -->\n| A | B |\n|:-|-:|\n| C | D |\n| E^ | F |", TableOperation::MoveRowUp, "<!-- table-fmt: sort=A -->\n| A    |    B |\n| :--- | ---: |\n| E    |    F |\n| C    |    D |"; "Move row up under sort directive")]
+#[test_case("> - | A | B |\n>   |:-|-:|\n>   | C^ |", TableOperation::InsertColumnRight, "> - | A    |     |    B |\n>   | :--- | --- | ---: |\n>   | C    |     |"; "Nested table with short row")]
+fn applies_operation(doc: &str, operation: TableOperation, correct: &str) {
+    assert_eq!(apply(doc, operation).as_deref(), Some(correct));
+}
+
+#[test_case("| ^A | B |\n|:-|-:|\n| C | D |", TableOperation::InsertRowAbove; "Insert row above header")]
+#[test_case("| ^A | B |\n|:-|-:|\n| C | D |", TableOperation::DeleteRow; "Delete header")]
+#[test_case("| A | B |\n|:-|-:|\n| ^C | D |", TableOperation::MoveRowUp; "Move first row up")]
+#[test_case("| A | B |\n|:-|-:|\n| ^C | D |", TableOperation::MoveRowDown; "Move last row down")]
+#[test_case("| ^A |\n|-|\n| C |", TableOperation::DeleteColumn; "Delete only column")]
+#[test_case("| A | B |\n|:-|-:|\n| C | D |^", TableOperation::MoveColumnRight; "Move last column right")]
+#[test_case("| A | B |\n|:-|-:|\n| C | D | ^E |", TableOperation::DeleteColumn; "Extra cell")]
+#[test_case("text^\n\n| A | B |\n|:-|-:|", TableOperation::InsertColumnLeft; "Outside of table")]
+fn operation_does_not_apply(doc: &str, operation: TableOperation) {
+    assert_eq!(apply(doc, operation), None);
+}
+
+#[test]
+fn header_row_as_lazy_list_continuation() {
+    // `comrak` reports the column of the list item content, which is inside `é` on the header row
+    let doc = apply("1. a|\n| é^ |\n    |-|", TableOperation::InsertColumnLeft).unwrap();
+
+    assert_eq!(doc, "1. a|\n|     | é   |\n    | --- | --- |");
+}
+
+#[test]
+fn edit_only_replaces_table() {
+    let offset = TABLE.find("D").unwrap();
+    let edit = edit_table(TABLE, offset, TableOperation::DeleteColumn).unwrap();
+
+    assert_eq!(
+        &TABLE[edit.range()],
+        "| A | B |\n|:-|-:|\n| C | D |\n| E | F |"
+    );
+    assert_eq!(edit.new_text, "| A    |\n| :--- |\n| C    |\n| E    |");
+    assert_eq!(edit.utf16_range(), edit.range());
+}
@@ -0,0 +1,60 @@
+use markdown_table_formatter::{
+    create_table_skeleton, create_table_skeleton_with, format_tables, FormatOptions,
+};
+use test_case::test_case;
+
+/// Return the new document with `^` inserted at the cursor.
+fn skeleton(doc: &str, line: usize) -> Option<String> {
+    create_table_skeleton(doc, line).map(|skeleton| {
+        let mut text = skeleton.text;
+        text.insert(skeleton.cursor, '^');
+        text
+    })
+}
+
+#[test_case("Name | Age | City", 1, "| Name | Age | City |\n| ---- | --- | ---- |\n^"; "Last line")]
+#[test_case("Name | Age\n", 1, "| Name | Age |\n| ---- | --- |\n^"; "Trailing newline")]
+#[test_case("Name | Age\n\ntext", 1, "| Name | Age |\n| ---- | --- |\n^\n\ntext"; "Keep blank line")]
+#[test_case("# Title\n\n|A|B|\n", 3, "# Title\n\n| A   | B   |\n| --- | --- |\n^"; "Leading and trailing bars")]
+#[test_case("Name | Age\r\n\r\n", 1, "| Name | Age |\r\n| ---- | --- |\r\n^\r\n\r\n"; "CRLF")]
+#[test_case("> A | B\n", 1, "> | A   | B   |\n> | --- | --- |\n> ^"; "Blockquote")]
+#[test_case("- A | B\n", 1, "- | A   | B   |\n  | --- | --- |\n  ^"; "List item")]
+#[test_case("text\nA | B\nmore", 2, "text\n| A   | B   |\n| --- | --- |\n^\nmore"; "Insert line after paragraph line")]
+fn creates_skeleton(doc: &str, line: usize, correct: &str) {
+    assert_eq!(skeleton(doc, line).as_deref(), Some(correct));
+}
+
+#[test_case("Name Age", 1; "No vertical bar")]
+#[test_case("Name \\| Age", 1; "Escaped vertical bar")]
+#[test_case("| |", 1; "Only empty cells")]
+#[test_case("|A|B|\n|-|-|", 1; "Already a table")]
+#[test_case("```\nA | B\n```", 2; "Code block")]
+#[test_case("A | B", 2; "Line out of range")]
+#[test_case("A | B", 0; "Line zero")]
+fn no_skeleton(doc: &str, line: usize) {
+    assert_eq!(skeleton(doc, line), None);
+}
+
+#[test]
+fn skeleton_is_formatted() {
+    let skeleton = create_table_skeleton("Name | Age | City\n", 1).unwrap();
+
+    assert_eq!(format_tables(&skeleton.text), skeleton.text);
+}
+
+#[test]
+fn skeleton_with_options() {
+    let options = FormatOptions::new().min_delimiter_dashes(1);
+    let skeleton = create_table_skeleton_with("A | B", 1, &options).unwrap();
+
+    assert_eq!(skeleton.text, "| A | B |\n| - | - |\n");
+}
+
+#[test]
+fn utf16_cursor() {
+    let skeleton = create_table_skeleton("🦀 | B", 1).unwrap();
+
+    assert_eq!(skeleton.text, "| 🦀  | B   |\n| --- | --- |\n");
+    assert_eq!(skeleton.cursor, skeleton.text.len());
+    assert_eq!(skeleton.utf16_cursor, skeleton.text.encode_utf16().count());
+}
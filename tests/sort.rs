@@ -0,0 +1,72 @@
+use markdown_table_formatter::{
+    sort_tables, sort_tables_with, FormatOptions, SortMethod, SortOptions,
+};
+use test_case::test_case;
+
+const TABLE: &str = "| Name | Size |\n| :--- | ---: |\n| b10  |    2 |\n| B9   |   10 |\n| a    |  1.5 |\n| b10  |    1 |";
+
+/// Return the first column of every body row after sorting `TABLE`.
+fn sorted_names(options: &SortOptions) -> Vec<String> {
+    sort_tables(TABLE, options)
+        .lines()
+        .skip(2)
+        .map(|line| line.split('|').nth(1).unwrap().trim().to_string())
+        .collect()
+}
+
+#[test_case(SortOptions::by_header("Name"), &["B9", "a", "b10", "b10"]; "Lexical")]
+#[test_case(SortOptions::by_header("Name").method(SortMethod::CaseInsensitive), &["a", "b10", "b10", "B9"]; "Case insensitive")]
+#[test_case(SortOptions::by_header("Name").method(SortMethod::Natural), &["B9", "a", "b10", "b10"]; "Natural")]
+#[test_case(SortOptions::by_index(1).method(SortMethod::Numeric), &["b10", "a", "b10", "B9"]; "Numeric")]
+#[test_case(SortOptions::by_index(1).method(SortMethod::Numeric).descending(true), &["B9", "b10", "a", "b10"]; "Numeric descending")]
+fn sorts_body_rows(options: SortOptions, correct: &[&str]) {
+    assert_eq!(sorted_names(&options), correct);
+}
+
+#[test]
+fn sort_is_stable() {
+    let ascending = sort_tables(TABLE, &SortOptions::by_header("Name"));
+    let descending = sort_tables(TABLE, &SortOptions::by_header("Name").descending(true));
+
+    assert!(ascending.ends_with("| b10  |    2 |\n| b10  |    1 |"));
+    assert!(descending.contains("| b10  |    2 |\n| b10  |    1 |"));
+}
+
+#[test_case(false, &["999", "1,234", "", "n/a"]; "Ascending")]
+#[test_case(true, &["1,234", "999", "n/a", ""]; "Descending")]
+fn numeric_sort_keeps_non_numbers_last(descending: bool, correct: &[&str]) {
+    let doc = "|A|\n|-|\n|n/a|\n|1,234|\n||\n|999|";
+    let options = SortOptions::by_index(0)
+        .method(SortMethod::Numeric)
+        .descending(descending);
+
+    let cells: Vec<String> = sort_tables(doc, &options)
+        .lines()
+        .skip(2)
+        .map(|line| line.trim_matches(['|', ' ']).to_string())
+        .collect();
+
+    assert_eq!(cells, correct);
+}
+
+#[test]
+fn sorted_table_is_formatted() {
+    let doc = "|A|B|\n|-|:-:|\n|y|1|\n|x|2|";
+    let options = FormatOptions::new().min_delimiter_dashes(1);
+
+    assert_eq!(
+        sort_tables_with(doc, &SortOptions::by_header("A"), &options),
+        "| A |  B  |\n| - | :-: |\n| x |  2  |\n| y |  1  |"
+    );
+}
+
+#[test]
+fn tables_without_column_are_untouched() {
+    let doc = "|A|\n|-|\n|b|\n|a|\n\n|B|\n|-|\n|b|\n|a|\n\n> |A|\n> |-|\n> |d|\n> |c|\n";
+
+    assert_eq!(
+        sort_tables(doc, &SortOptions::by_header("A")),
+        "| A   |\n| --- |\n| a   |\n| b   |\n\n|B|\n|-|\n|b|\n|a|\n\n> | A   |\n> | --- |\n> | c   |\n> | d   |\n"
+    );
+    assert_eq!(sort_tables(doc, &SortOptions::by_index(1)), doc);
+}
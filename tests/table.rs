@@ -0,0 +1,129 @@
+use markdown_table_formatter::{extract_tables, format_tables, Alignment, FormatOptions, Table};
+use test_case::test_case;
+
+const BASIC_TABLE: &str = include_str!("text_snippets/basic_table.txt");
+const TABLE_ALIGNMENT: &str = include_str!("text_snippets/table_alignment.txt");
+const TABLE_IN_BLOCKQUOTE: &str = include_str!("text_snippets/table_in_blockquote.txt");
+const TABLE_IN_LIST: &str = include_str!("text_snippets/table_in_list.txt");
+const TABLE_IN_FOOTNOTE: &str = include_str!("text_snippets/table_in_footnote.txt");
+const INTERSPERSED_TABLES: &str = include_str!("text_snippets/interspersed_tables.txt");
+const TABLE_WITH_TRAILING_TEXT: &str = include_str!("text_snippets/table_with_trailing_text.txt");
+
+/// Replace every table in `doc` with its rendered Markdown.
+fn render_all(doc: &str) -> String {
+    let mut rendered = doc.to_string();
+
+    for table in extract_tables(doc).iter().rev() {
+        rendered.replace_range(
+            table.range.clone(),
+            &table.to_markdown(&FormatOptions::default()),
+        );
+    }
+
+    rendered
+}
+
+#[test_case(BASIC_TABLE)]
+#[test_case(TABLE_ALIGNMENT)]
+#[test_case(TABLE_IN_BLOCKQUOTE)]
+#[test_case(TABLE_IN_LIST)]
+#[test_case(TABLE_IN_FOOTNOTE)]
+#[test_case(INTERSPERSED_TABLES)]
+#[test_case(TABLE_WITH_TRAILING_TEXT)]
+fn round_trip_matches_format_tables(doc: &str) {
+    assert_eq!(render_all(doc), format_tables(doc));
+}
+
+#[test]
+fn extract_table_contents() {
+    let doc = "# Title\n\n> | A | B |\n> |:-|-:|\n> | C | D | E |\n> F\n";
+    let tables = extract_tables(doc);
+
+    assert_eq!(tables.len(), 1);
+
+    let table = &tables[0];
+    assert_eq!(table.headers, vec!["A", "B"]);
+    assert_eq!(table.alignments, vec![Alignment::Left, Alignment::Right]);
+    assert_eq!(
+        table.rows,
+        vec![vec!["C", "D", "E"], vec!["F"]]
+            .into_iter()
+            .map(|row| row.into_iter().map(String::from).collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        &doc[table.range.clone()],
+        "> | A | B |\n> |:-|-:|\n> | C | D | E |\n> F"
+    );
+    assert_eq!(table.lines, 3..=6);
+}
+
+#[test]
+fn edit_and_render() {
+    let doc = "| A | B |\n|-|:-:|\n| C | D |";
+    let mut table = extract_tables(doc).remove(0);
+
+    table.rows[0][1] = String::from("changed");
+    table
+        .rows
+        .push(vec![String::from("new"), String::from("row")]);
+
+    assert_eq!(
+        table.to_markdown(&FormatOptions::default()),
+        "| A   |    B    |\n| --- | :-----: |\n| C   | changed |\n| new |   row   |"
+    );
+}
+
+#[test]
+fn new_table() {
+    let table = Table::new(
+        vec![String::from("Name"), String::from("Size")],
+        vec![Alignment::None, Alignment::Right],
+        vec![vec![String::from("a.txt"), String::from("12")]],
+    );
+
+    assert_eq!(
+        table.to_markdown(&FormatOptions::default()),
+        "| Name  | Size |\n| ----- | ---: |\n| a.txt |   12 |"
+    );
+}
+
+#[test_case(vec!["A"], vec![Alignment::None; 2], "| A   |     |\n| --- | --- |"; "Fewer headers than alignments")]
+#[test_case(vec!["A", "B"], vec![Alignment::Right], "|    A | B   |\n| ---: | --- |"; "More headers than alignments")]
+fn headers_and_alignments_are_reconciled(
+    headers: Vec<&str>,
+    alignments: Vec<Alignment>,
+    correct: &str,
+) {
+    let headers = headers.into_iter().map(String::from).collect();
+    let markdown = Table::new(headers, alignments, vec![]).to_markdown(&FormatOptions::default());
+
+    assert_eq!(markdown, correct);
+    assert_eq!(extract_tables(&markdown).len(), 1);
+}
+
+#[test]
+fn insert_and_move_columns() {
+    let mut table = Table::new(
+        vec![String::from("A"), String::from("B")],
+        vec![Alignment::Left, Alignment::Right],
+        vec![
+            vec![String::from("C"), String::from("D")],
+            vec![String::from("E")],
+        ],
+    );
+
+    table.insert_column(1, Alignment::Center);
+    table.move_column(0, 2);
+    table.delete_column(1);
+
+    assert_eq!(table.headers, vec!["", "A"]);
+    assert_eq!(table.alignments, vec![Alignment::Center, Alignment::Left]);
+    assert_eq!(
+        table.rows,
+        vec![
+            vec![String::from(""), String::from("C")],
+            vec![String::from("E")]
+        ]
+    );
+}